@@ -1,8 +1,11 @@
 use crate::components;
-use crate::game;
+use crate::config;
+use crate::effects::status::StatusKind;
+use crate::faction;
+use crate::game::turns;
 use crate::map;
 use rltk;
-use rltk::Point;
+use rltk::{Point, RandomNumberGenerator};
 use specs::{
     self, Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage,
 };
@@ -13,79 +16,126 @@ impl<'a> System<'a> for MonsterAI {
     #[allow(clippy::type_complexity)]
     type SystemData = (
         WriteExpect<'a, map::Map>,
-        ReadExpect<'a, Point>,
-        ReadExpect<'a, Entity>,
-        ReadExpect<'a, game::state::RunState>,
+        ReadExpect<'a, config::AppConfig>,
         Entities<'a>,
         WriteStorage<'a, components::Viewshed>,
+        WriteStorage<'a, components::Energy>,
         ReadStorage<'a, components::Monster>,
+        ReadStorage<'a, components::Faction>,
+        ReadStorage<'a, components::CombatStats>,
         WriteStorage<'a, components::Position>,
         WriteStorage<'a, components::WantsToMelee>,
-        WriteStorage<'a, components::Confusion>,
+        ReadStorage<'a, components::StatusEffects>,
+        WriteExpect<'a, RandomNumberGenerator>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (
             mut game_map,
-            player_pos,
-            player_entity,
-            runstate,
+            cfg,
             entities,
             mut viewshed,
+            mut energies,
             monster,
+            factions,
+            combat_stats,
             mut position,
             mut wants_to_melee,
-            mut confused,
+            statuses,
+            mut rng,
         ) = data;
 
-        if *runstate != game::state::RunState::MonsterTurn {
-            return;
-        }
-
-        for (entity, mut viewshed, _monster, mut pos) in
-            (&entities, &mut viewshed, &monster, &mut position).join()
+        for (entity, viewshed, energy, _monster, pos) in (
+            &entities,
+            &mut viewshed,
+            &mut energies,
+            &monster,
+            &mut position,
+        )
+            .join()
         {
+            if !energy.can_act(&cfg.turns) {
+                continue;
+            }
+            // Whatever happens below, the time is spent
+            let mut action = turns::Action::Wait;
             let mut can_act = true;
 
-            let is_confused = confused.get_mut(entity);
-            if let Some(i_am_confused) = is_confused {
-                i_am_confused.turns -= 1;
-                if i_am_confused.turns < 1 {
-                    confused.remove(entity);
-                }
+            let afflicted = statuses.get(entity);
+            if afflicted.map_or(false, |s| s.has(StatusKind::Paralysis)) {
                 can_act = false;
+            } else if afflicted.map_or(false, |s| s.has(StatusKind::Confusion)) {
+                // Stumble about in a random direction
+                can_act = false;
+                let x = pos.x + rng.range(-1, 2);
+                let y = pos.y + rng.range(-1, 2);
+                if x > 0 && x < game_map.width - 1 && y > 0 && y < game_map.height - 1 {
+                    let destination_idx = game_map.xy_idx(x, y);
+                    if !game_map.blocked[destination_idx] {
+                        let idx = game_map.xy_idx(pos.x, pos.y);
+                        game_map.blocked[idx] = false;
+                        pos.x = x;
+                        pos.y = y;
+                        game_map.blocked[destination_idx] = true;
+                        viewshed.dirty = true;
+                        action = turns::Action::Move;
+                    }
+                }
             }
 
             if can_act {
-                let distance =
-                    rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos);
-                if distance < 1.5 {
-                    wants_to_melee
-                        .insert(
-                            entity,
-                            components::WantsToMelee {
-                                target: *player_entity,
-                            },
-                        )
-                        .expect("Unable to insert attack");
-                } else if viewshed.visible_tiles.contains(&*player_pos) {
-                    // Path to the player
-                    let path = rltk::a_star_search(
-                        game_map.xy_idx(pos.x, pos.y),
-                        game_map.xy_idx(player_pos.x, player_pos.y),
-                        &mut *game_map,
-                    );
-                    if path.success && path.steps.len() > 1 {
-                        let mut idx = game_map.xy_idx(pos.x, pos.y);
-                        game_map.blocked[idx] = false;
-                        pos.x = path.steps[1] as i32 % game_map.width;
-                        pos.y = path.steps[1] as i32 / game_map.width;
-                        idx = game_map.xy_idx(pos.x, pos.y);
-                        game_map.blocked[idx] = true;
-                        viewshed.dirty = true;
+                // Pick the closest visible thing we're hostile towards
+                let here = Point::new(pos.x, pos.y);
+                let mut target: Option<(Entity, Point, f32)> = None;
+                for tile in viewshed.visible_tiles.iter() {
+                    let idx = game_map.xy_idx(tile.x, tile.y);
+                    for other in game_map.tile_content[idx].iter() {
+                        if *other == entity || combat_stats.get(*other).is_none() {
+                            continue;
+                        }
+                        let reaction = faction::between(
+                            &cfg.factions,
+                            factions.get(entity),
+                            factions.get(*other),
+                        );
+                        if reaction != faction::Reaction::Hostile {
+                            continue;
+                        }
+                        let distance = rltk::DistanceAlg::Pythagoras.distance2d(here, *tile);
+                        if target.map_or(true, |(_, _, closest)| distance < closest) {
+                            target = Some((*other, *tile, distance));
+                        }
+                    }
+                }
+
+                if let Some((target, target_pos, distance)) = target {
+                    if distance < 1.5 {
+                        wants_to_melee
+                            .insert(entity, components::WantsToMelee { target })
+                            .expect("Unable to insert attack");
+                        action = turns::Action::Attack;
+                    } else {
+                        // Path to the target
+                        let path = rltk::a_star_search(
+                            game_map.xy_idx(pos.x, pos.y),
+                            game_map.xy_idx(target_pos.x, target_pos.y),
+                            &mut *game_map,
+                        );
+                        if path.success && path.steps.len() > 1 {
+                            let mut idx = game_map.xy_idx(pos.x, pos.y);
+                            game_map.blocked[idx] = false;
+                            pos.x = path.steps[1] as i32 % game_map.width;
+                            pos.y = path.steps[1] as i32 / game_map.width;
+                            idx = game_map.xy_idx(pos.x, pos.y);
+                            game_map.blocked[idx] = true;
+                            viewshed.dirty = true;
+                            action = turns::Action::Move;
+                        }
                     }
                 }
             }
+
+            energy.spend(action, &cfg.turns);
         }
     }
 }
@@ -1,21 +1,110 @@
 use crate::components;
+use crate::config;
 use crate::game;
+use crate::game::log::{Category, Highlight, LogEntry};
+use crate::items::equipment;
+use crate::player::progression;
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 use specs::{self, Join, System};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DamageType {
+    Physical,
+    Fire,
+    Cold,
+    Poison,
+    Arcane,
+    /// What starving does; no armour soaks it up and nobody resists it
+    Starvation,
+}
+
+impl Default for DamageType {
+    fn default() -> Self {
+        DamageType::Physical
+    }
+}
+
+impl fmt::Display for DamageType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            DamageType::Physical => "physical",
+            DamageType::Fire => "fire",
+            DamageType::Cold => "cold",
+            DamageType::Poison => "poison",
+            DamageType::Arcane => "arcane",
+            DamageType::Starvation => "starvation",
+        };
+        write!(f, "{}", name)
+    }
+}
 
 pub struct DamageSystem {}
 
 impl<'a> System<'a> for DamageSystem {
+    #[allow(clippy::type_complexity)]
     type SystemData = (
+        ReadExpect<'a, config::AppConfig>,
+        Entities<'a>,
+        WriteExpect<'a, game::log::GameLog>,
+        ReadStorage<'a, components::Name>,
+        ReadStorage<'a, components::Resistant>,
+        ReadStorage<'a, components::Vulnerable>,
         WriteStorage<'a, components::CombatStats>,
         WriteStorage<'a, components::SufferDamage>,
+        ReadStorage<'a, components::Equipped>,
+        ReadStorage<'a, components::EquipmentBonus>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut stats, mut damage) = data;
+        let (
+            cfg,
+            entities,
+            mut log,
+            names,
+            resistant,
+            vulnerable,
+            mut stats,
+            mut damage,
+            equipped,
+            bonuses,
+        ) = data;
 
-        for (mut stats, damage) in (&mut stats, &damage).join() {
-            stats.hp -= damage.amount.iter().sum::<i32>();
+        for (entity, stats, damage) in (&entities, &mut stats, &damage).join() {
+            let name = names.get(entity);
+            let absorb = equipment::bonuses(entity, &equipped, &bonuses).absorb;
+            for (amount, damage_type) in damage.amount.iter() {
+                let mut amount = *amount;
+                // Armour only soaks up blows, not fire or poison
+                if *damage_type == DamageType::Physical && absorb > 0 {
+                    amount = i32::max(0, amount - absorb);
+                }
+                if let Some(resists) = resistant.get(entity) {
+                    if resists.damage_types.contains(damage_type) {
+                        amount = amount * cfg.combat.resistance_percent / 100;
+                        if let Some(name) = name {
+                            log.add(
+                                Category::Combat,
+                                format!("{} resists the {} damage.", name.name, damage_type),
+                            );
+                        }
+                    }
+                }
+                if let Some(weaknesses) = vulnerable.get(entity) {
+                    if weaknesses.damage_types.contains(damage_type) {
+                        amount = amount * cfg.combat.vulnerability_percent / 100;
+                        if let Some(name) = name {
+                            log.add(
+                                Category::Combat,
+                                format!("{} is vulnerable to {} damage.", name.name, damage_type),
+                            );
+                        }
+                    }
+                }
+                stats.hp -= amount;
+            }
         }
 
         damage.clear();
@@ -24,11 +113,14 @@ impl<'a> System<'a> for DamageSystem {
 
 pub fn delete_the_dead(ecs: &mut World) {
     let mut dead: Vec<Entity> = Vec::new();
+    let mut rewards: Vec<(Entity, i32)> = Vec::new();
     // Using a scope to make the borrow checker happy
     {
         let combat_stats = ecs.read_storage::<components::CombatStats>();
         let names = ecs.read_storage::<components::Name>();
         let players = ecs.read_storage::<components::Player>();
+        let last_hit = ecs.read_storage::<components::LastHitBy>();
+        let values = ecs.read_storage::<components::ExperienceValue>();
         let entities = ecs.entities();
         let mut log = ecs.write_resource::<game::log::GameLog>();
         for (entity, stats) in (&entities, &combat_stats).join() {
@@ -38,16 +130,38 @@ pub fn delete_the_dead(ecs: &mut World) {
                     None => {
                         let victim_name = names.get(entity);
                         if let Some(victim_name) = victim_name {
-                            log.entries.push(format!("{} is dead", &victim_name.name));
+                            log.push(LogEntry::new(Category::Combat).highlighted(
+                                format!("{} is dead", &victim_name.name),
+                                Highlight::Danger,
+                            ));
+                        }
+                        if let Some(killer) = last_hit.get(entity) {
+                            let xp = values.get(entity).map_or(0, |value| value.xp);
+                            rewards.push((killer.attacker, xp));
                         }
                         dead.push(entity)
                     }
-                    Some(_) => log.entries.push(String::from("You are dead")),
+                    Some(_) => log.push(
+                        LogEntry::new(Category::Combat)
+                            .highlighted("You are dead", Highlight::Danger),
+                    ),
                 }
             }
         }
     }
 
+    for (killer, xp) in rewards {
+        if let Some(statistics) = ecs
+            .write_storage::<components::Statistics>()
+            .get_mut(killer)
+        {
+            statistics.kills += 1;
+        }
+        if xp > 0 {
+            progression::award(ecs, killer, xp);
+        }
+    }
+
     for victim in dead {
         ecs.delete_entity(victim).expect("Unable to delete");
     }
@@ -58,12 +172,13 @@ impl components::SufferDamage {
         store: &mut WriteStorage<components::SufferDamage>,
         victim: Entity,
         amount: i32,
+        damage_type: DamageType,
     ) {
         if let Some(suffering) = store.get_mut(victim) {
-            suffering.amount.push(amount);
+            suffering.amount.push((amount, damage_type));
         } else {
             let dmg = components::SufferDamage {
-                amount: vec![amount],
+                amount: vec![(amount, damage_type)],
             };
             store.insert(victim, dmg).expect("Unable to insert damage");
         }
@@ -0,0 +1,143 @@
+use rltk::RandomNumberGenerator;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A dice expression such as `1d6+2`: roll `count` dice with `sides` sides
+/// each and add `bonus` (which may be negative) to the total. A plain number
+/// such as `3` is a constant with no dice at all.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Dice {
+    pub count: i32,
+    pub sides: i32,
+    pub bonus: i32,
+}
+
+impl Dice {
+    pub fn parse(expr: &str) -> Result<Dice, String> {
+        let expr: String = expr.chars().filter(|c| !c.is_whitespace()).collect();
+        let invalid = || format!("Invalid dice expression '{}'", expr);
+        if expr.is_empty() {
+            return Err(invalid());
+        }
+
+        // Skip the first character so a leading minus isn't taken as a bonus
+        let sign = expr
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| *c == '+' || *c == '-')
+            .map(|(i, _)| i);
+        let (dice, bonus) = match sign {
+            None => (&expr[..], 0),
+            Some(i) => (&expr[..i], expr[i..].parse::<i32>().map_err(|_| invalid())?),
+        };
+        match dice.find(|c| c == 'd' || c == 'D') {
+            None => Ok(Dice {
+                count: 0,
+                sides: 0,
+                bonus: dice.parse::<i32>().map_err(|_| invalid())? + bonus,
+            }),
+            Some(i) => {
+                let count = if i == 0 {
+                    1
+                } else {
+                    dice[..i].parse::<i32>().map_err(|_| invalid())?
+                };
+                let sides = dice[i + 1..].parse::<i32>().map_err(|_| invalid())?;
+                if count < 0 || sides < 1 {
+                    return Err(invalid());
+                }
+                Ok(Dice {
+                    count,
+                    sides,
+                    bonus,
+                })
+            }
+        }
+    }
+
+    pub fn roll(&self, rng: &mut RandomNumberGenerator) -> i32 {
+        let rolled = if self.count > 0 {
+            rng.roll_dice(self.count, self.sides)
+        } else {
+            0
+        };
+        rolled + self.bonus
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.count == 0 {
+            return write!(f, "{}", self.bonus);
+        }
+        write!(f, "{}d{}", self.count, self.sides)?;
+        if self.bonus != 0 {
+            write!(f, "{:+}", self.bonus)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for Dice {
+    type Error = String;
+
+    fn try_from(expr: String) -> Result<Self, Self::Error> {
+        Dice::parse(&expr)
+    }
+}
+
+impl From<Dice> for String {
+    fn from(dice: Dice) -> Self {
+        dice.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dice_with_and_without_a_bonus() {
+        let expected = Dice {
+            count: 2,
+            sides: 6,
+            bonus: 3,
+        };
+        assert_eq!(Dice::parse("2d6+3"), Ok(expected));
+        assert_eq!(Dice::parse(" 2D6 + 3 "), Ok(expected));
+        assert_eq!(
+            Dice::parse("d8-1"),
+            Ok(Dice {
+                count: 1,
+                sides: 8,
+                bonus: -1
+            })
+        );
+        assert_eq!(
+            Dice::parse("-2"),
+            Ok(Dice {
+                count: 0,
+                sides: 0,
+                bonus: -2
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expr in &["", "d", "2d", "2d0", "xd6", "1d6+", "1d6+x"] {
+            assert!(Dice::parse(expr).is_err(), "'{}' should not parse", expr);
+        }
+    }
+
+    #[test]
+    fn display_round_trips_through_parse() {
+        for expr in &["1d6", "2d4+1", "3d8-2", "5", "-1", "0"] {
+            let dice = Dice::parse(expr).unwrap();
+            assert_eq!(dice.to_string(), *expr);
+            assert_eq!(Dice::parse(&dice.to_string()), Ok(dice));
+        }
+    }
+}
@@ -1,47 +1,134 @@
 use crate::components;
+use crate::config;
 use crate::game;
+use crate::game::log::{Category, Highlight, LogEntry};
+use crate::items::equipment;
+use rltk::RandomNumberGenerator;
 use specs::prelude::*;
 use specs::{self, Join, System};
 
 pub struct MeleeSystem {}
 
 impl<'a> System<'a> for MeleeSystem {
+    #[allow(clippy::type_complexity)]
     type SystemData = (
         Entities<'a>,
+        ReadExpect<'a, config::AppConfig>,
         WriteExpect<'a, game::log::GameLog>,
+        WriteExpect<'a, RandomNumberGenerator>,
         WriteStorage<'a, components::WantsToMelee>,
         ReadStorage<'a, components::Name>,
         ReadStorage<'a, components::CombatStats>,
         WriteStorage<'a, components::SufferDamage>,
+        ReadStorage<'a, components::Equipped>,
+        ReadStorage<'a, components::EquipmentBonus>,
+        WriteStorage<'a, components::LastHitBy>,
+        ReadStorage<'a, components::Hunger>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, mut log, mut wants_melee, names, combat_stats, mut inflict_damage) = data;
+        let (
+            entities,
+            cfg,
+            mut log,
+            mut rng,
+            mut wants_melee,
+            names,
+            combat_stats,
+            mut inflict_damage,
+            equipped,
+            bonuses,
+            mut last_hit,
+            hunger,
+        ) = data;
 
-        for (_entity, wants_melee, name, stats) in
+        for (entity, wants_melee, name, stats) in
             (&entities, &wants_melee, &names, &combat_stats).join()
         {
             if stats.hp > 0 {
                 let target_stats = combat_stats.get(wants_melee.target).unwrap();
                 if target_stats.hp > 0 {
                     let target_name = names.get(wants_melee.target).unwrap();
-                    let damage = i32::max(0, stats.power - target_stats.defense);
+                    let gear = equipment::bonuses(entity, &equipped, &bonuses);
+                    let target_gear = equipment::bonuses(wants_melee.target, &equipped, &bonuses);
+                    let damage_dice = gear.damage.unwrap_or(stats.damage);
+                    let damage_type = gear.damage_type.unwrap_or(stats.damage_type);
 
-                    if damage == 0 {
-                        log.entries.push(format!(
-                            "{} is unable to hurt {}",
-                            &name.name, &target_name.name
-                        ));
-                    } else {
-                        log.entries.push(format!(
-                            "{} hits {}, for {} hp.",
-                            &name.name, &target_name.name, damage
-                        ));
+                    let natural_roll = rng.roll_dice(1, cfg.combat.to_hit_die);
+                    let weakness = hunger
+                        .get(entity)
+                        .map_or(0, |h| h.state.penalty(&cfg.hunger));
+                    let to_hit = natural_roll + stats.power + gear.power - weakness;
+                    let evasion =
+                        cfg.combat.base_evasion + target_stats.defense + target_gear.defense;
+
+                    if natural_roll == 1 {
+                        log.add(
+                            Category::Combat,
+                            format!(
+                                "{} fumbles the attack on {}.",
+                                &name.name, &target_name.name
+                            ),
+                        );
+                    } else if natural_roll == cfg.combat.to_hit_die {
+                        let damage = i32::max(1, damage_dice.roll(&mut rng))
+                            * cfg.combat.critical_multiplier;
+                        log.push(
+                            LogEntry::new(Category::Combat)
+                                .text(format!(
+                                    "{} critically hits {}, for ",
+                                    &name.name, &target_name.name
+                                ))
+                                .highlighted(format!("{} hp", damage), Highlight::Warning)
+                                .text("!"),
+                        );
+                        last_hit
+                            .insert(
+                                wants_melee.target,
+                                components::LastHitBy { attacker: entity },
+                            )
+                            .expect("Unable to insert last hit");
                         components::SufferDamage::new_damage(
                             &mut inflict_damage,
                             wants_melee.target,
                             damage,
+                            damage_type,
+                        );
+                    } else if to_hit < evasion {
+                        log.add(
+                            Category::Combat,
+                            format!("{} misses {}.", &name.name, &target_name.name),
                         );
+                    } else {
+                        let damage = i32::max(0, damage_dice.roll(&mut rng));
+                        if damage == 0 {
+                            log.add(
+                                Category::Combat,
+                                format!("{} is unable to hurt {}", &name.name, &target_name.name),
+                            );
+                        } else {
+                            log.push(
+                                LogEntry::new(Category::Combat)
+                                    .text(format!(
+                                        "{} hits {}, for ",
+                                        &name.name, &target_name.name
+                                    ))
+                                    .highlighted(format!("{} hp", damage), Highlight::Danger)
+                                    .text("."),
+                            );
+                            last_hit
+                                .insert(
+                                    wants_melee.target,
+                                    components::LastHitBy { attacker: entity },
+                                )
+                                .expect("Unable to insert last hit");
+                            components::SufferDamage::new_damage(
+                                &mut inflict_damage,
+                                wants_melee.target,
+                                damage,
+                                damage_type,
+                            );
+                        }
                     }
                 }
             }
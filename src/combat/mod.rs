@@ -1,2 +1,4 @@
 pub mod damage;
+pub mod dice;
 pub mod melee;
+pub mod projectile;
@@ -0,0 +1,119 @@
+use crate::map;
+use rltk::Point;
+
+/// The tiles on a straight line between two points, using Bresenham's
+/// algorithm. The starting tile isn't included; the end tile is.
+pub fn line(from: Point, to: Point) -> Vec<Point> {
+    let mut points = Vec::new();
+    let dx = i32::abs(to.x - from.x);
+    let dy = -i32::abs(to.y - from.y);
+    let step_x = if from.x < to.x { 1 } else { -1 };
+    let step_y = if from.y < to.y { 1 } else { -1 };
+    let mut error = dx + dy;
+    let (mut x, mut y) = (from.x, from.y);
+
+    while x != to.x || y != to.y {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+        points.push(Point::new(x, y));
+    }
+    points
+}
+
+/// Follows a bolt from the shooter towards the target. It flies until it
+/// reaches the target, hits a wall or hits something standing in the way,
+/// whichever comes first; the last tile returned is where it lands.
+pub fn trace(game_map: &map::Map, from: Point, target: Point) -> Vec<Point> {
+    let mut path = Vec::new();
+    for pos in line(from, target) {
+        if pos.x < 0 || pos.y < 0 || pos.x >= game_map.width || pos.y >= game_map.height {
+            break;
+        }
+        path.push(pos);
+        let idx = game_map.xy_idx(pos.x, pos.y);
+        if game_map.blocked[idx] {
+            break;
+        }
+    }
+    path
+}
+
+/// Where a bolt aimed at the target actually lands. Aiming at one's own tile
+/// lands there.
+pub fn impact(game_map: &map::Map, from: Point, target: Point) -> Point {
+    trace(game_map, from, target)
+        .last()
+        .copied()
+        .unwrap_or(target)
+}
+
+/// Where something thrown at the target comes to rest. Unlike a bolt, it
+/// can't end up inside a wall, so it drops on the last open tile before one.
+pub fn landing(game_map: &map::Map, from: Point, target: Point) -> Point {
+    let path = trace(game_map, from, target);
+    match path.last() {
+        None => target,
+        Some(pos) if game_map.tiles[game_map.xy_idx(pos.x, pos.y)] == map::TileType::Wall => {
+            path.iter().rev().nth(1).copied().unwrap_or(from)
+        }
+        Some(pos) => *pos,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_ends_on_the_target_and_skips_the_start() {
+        let from = Point::new(5, 5);
+        for to in &[
+            Point::new(9, 6),
+            Point::new(6, 9),
+            Point::new(4, 9),
+            Point::new(1, 6),
+            Point::new(1, 4),
+            Point::new(4, 1),
+            Point::new(6, 1),
+            Point::new(9, 4),
+        ] {
+            let points = line(from, *to);
+            assert_eq!(points.last(), Some(to));
+            assert!(!points.contains(&from));
+        }
+    }
+
+    #[test]
+    fn line_takes_one_step_per_tile_along_the_longer_axis() {
+        let from = Point::new(0, 0);
+        let to = Point::new(7, -3);
+        let points = line(from, to);
+        assert_eq!(points.len(), 7);
+        let mut previous = from;
+        for pos in points {
+            assert_eq!(pos.x - previous.x, 1);
+            assert!(previous.y - pos.y == 0 || previous.y - pos.y == 1);
+            previous = pos;
+        }
+    }
+
+    #[test]
+    fn line_follows_straight_and_diagonal_lines_exactly() {
+        assert_eq!(
+            line(Point::new(2, 2), Point::new(2, 5)),
+            vec![Point::new(2, 3), Point::new(2, 4), Point::new(2, 5)]
+        );
+        assert_eq!(
+            line(Point::new(2, 2), Point::new(-1, -1)),
+            vec![Point::new(1, 1), Point::new(0, 0), Point::new(-1, -1)]
+        );
+        assert!(line(Point::new(3, 3), Point::new(3, 3)).is_empty());
+    }
+}
@@ -1,4 +1,9 @@
+use crate::combat::damage::DamageType;
+use crate::combat::dice;
+use crate::effects::status::StatusEffect;
+use crate::items::equipment::EquipmentSlot;
 use crate::map;
+use crate::player::hunger::HungerState;
 use rltk::RGB;
 use serde::{Deserialize, Serialize};
 use specs::error::NoError;
@@ -41,12 +46,35 @@ pub struct Name {
 #[derive(Clone, Component, Debug, Deserialize, Serialize)]
 pub struct BlocksTile {}
 
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct Faction {
+    pub name: String,
+}
+
 #[derive(Clone, Component, ConvertSaveload, Debug)]
 pub struct CombatStats {
     pub max_hp: i32,
     pub hp: i32,
     pub defense: i32,
     pub power: i32,
+    pub damage: dice::Dice,
+    pub damage_type: DamageType,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct Resistant {
+    pub damage_types: Vec<DamageType>,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct Vulnerable {
+    pub damage_types: Vec<DamageType>,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct Energy {
+    pub speed: i32,
+    pub current: i32,
 }
 
 #[derive(Clone, Component, ConvertSaveload, Debug)]
@@ -56,7 +84,56 @@ pub struct WantsToMelee {
 
 #[derive(Clone, Component, ConvertSaveload, Debug)]
 pub struct SufferDamage {
-    pub amount: Vec<i32>,
+    pub amount: Vec<(i32, DamageType)>,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct LastHitBy {
+    pub attacker: Entity,
+}
+
+/// Whoever last put a status effect on an entity, so that damage over time
+/// is credited to them.
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct AfflictedBy {
+    pub attacker: Entity,
+}
+
+#[derive(Clone, Component, Debug, Deserialize, Serialize)]
+pub struct Experience {
+    pub level: i32,
+    pub xp: i32,
+    pub unspent: i32,
+}
+
+#[derive(Clone, Component, Debug, Deserialize, Serialize)]
+pub struct ExperienceValue {
+    pub xp: i32,
+}
+
+/// A running tally of how the player's game has gone.
+#[derive(Clone, Component, Debug, Default, Deserialize, Serialize)]
+pub struct Statistics {
+    pub turns_taken: i32,
+    pub kills: i32,
+}
+
+#[derive(Clone, Component, Debug, Deserialize, Serialize)]
+pub struct Hunger {
+    pub state: HungerState,
+    pub clock: i32,
+}
+
+#[derive(Clone, Component, Debug, Deserialize, Serialize)]
+pub struct NaturalRegeneration {
+    pub interval: i32,
+    pub amount: i32,
+    pub counter: i32,
+}
+
+#[derive(Clone, Component, Debug, Deserialize, Serialize)]
+pub struct ProvidesFood {
+    pub nutrition: i32,
 }
 
 #[derive(Clone, Component, Debug, Deserialize, Serialize)]
@@ -65,6 +142,12 @@ pub struct Item {}
 #[derive(Clone, Component, Debug, Deserialize, Serialize)]
 pub struct Consumable {}
 
+/// How heavy an item is, counted against what its owner can carry.
+#[derive(Clone, Component, Debug, Deserialize, Serialize)]
+pub struct Weight {
+    pub weight: i32,
+}
+
 #[derive(Clone, Component, ConvertSaveload, Debug)]
 pub struct Ranged {
     pub range: i32,
@@ -73,6 +156,7 @@ pub struct Ranged {
 #[derive(Clone, Component, ConvertSaveload, Debug)]
 pub struct InflictsDamage {
     pub damage: i32,
+    pub damage_type: DamageType,
 }
 
 #[derive(Clone, Component, ConvertSaveload, Debug)]
@@ -81,8 +165,13 @@ pub struct AreaOfEffect {
 }
 
 #[derive(Clone, Component, ConvertSaveload, Debug)]
-pub struct Confusion {
-    pub turns: i32,
+pub struct InflictsStatus {
+    pub effect: StatusEffect,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
 }
 
 #[derive(Clone, Component, ConvertSaveload, Debug)]
@@ -95,6 +184,46 @@ pub struct InBackpack {
     pub owner: Entity,
 }
 
+/// The letter each kind of item is listed under in the player's inventory,
+/// kept so that the same item is always picked with the same key.
+#[derive(Clone, Component, Debug, Default, Deserialize, Serialize)]
+pub struct InventoryLetters {
+    pub letters: Vec<(String, char)>,
+}
+
+impl InventoryLetters {
+    /// The letter a kind of item is listed under, if it's been given one.
+    pub fn letter(&self, name: &str) -> Option<char> {
+        self.letters
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, letter)| *letter)
+    }
+
+    /// The letter a kind of item is listed under, handing out the first free
+    /// one if it hasn't got one yet. Once all of them have been used, the
+    /// letters of things no longer carried are given up.
+    pub fn letter_for(&mut self, name: &str, carried: &[String]) -> Option<char> {
+        if let Some(letter) = self.letter(name) {
+            return Some(letter);
+        }
+        if self.letters.len() >= 26 {
+            self.letters.retain(|(n, _)| carried.contains(n));
+        }
+        let letter = (b'a'..=b'z')
+            .map(|c| c as char)
+            .find(|c| !self.letters.iter().any(|(_, l)| l == c))?;
+        self.letters.push((name.to_string(), letter));
+        Some(letter)
+    }
+}
+
+/// The kind of item in each of the player's hotbar slots, by name.
+#[derive(Clone, Component, Debug, Default, Deserialize, Serialize)]
+pub struct Hotbar {
+    pub slots: Vec<Option<String>>,
+}
+
 #[derive(Clone, Component, ConvertSaveload, Debug)]
 pub struct WantsToPickupItem {
     pub collected_by: Entity,
@@ -112,6 +241,42 @@ pub struct WantsToDropItem {
     pub item: Entity,
 }
 
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct WantsToThrowItem {
+    pub item: Entity,
+    pub target: rltk::Point,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct Equippable {
+    pub slot: EquipmentSlot,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct Equipped {
+    pub owner: Entity,
+    pub slot: EquipmentSlot,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct EquipmentBonus {
+    pub power: i32,
+    pub defense: i32,
+    pub absorb: i32,
+    pub damage: Option<dice::Dice>,
+    pub damage_type: Option<DamageType>,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct WantsToEquipItem {
+    pub item: Entity,
+}
+
+#[derive(Clone, Component, ConvertSaveload, Debug)]
+pub struct WantsToRemoveItem {
+    pub item: Entity,
+}
+
 // Serialization helper code. We need to implement ConvertSaveload for each type that contains an
 // Entity.
 
@@ -122,3 +287,52 @@ pub struct SerializeMe;
 pub struct SerializationHelper {
     pub map: map::Map,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("Item {}", i)).collect()
+    }
+
+    #[test]
+    fn letters_are_handed_out_in_order_and_kept() {
+        let mut letters = InventoryLetters::default();
+        let carried = names(2);
+        assert_eq!(letters.letter_for("Item 0", &carried), Some('a'));
+        assert_eq!(letters.letter_for("Item 1", &carried), Some('b'));
+        assert_eq!(letters.letter_for("Item 0", &carried), Some('a'));
+        assert_eq!(letters.letter("Item 1"), Some('b'));
+        assert_eq!(letters.letter("Item 2"), None);
+    }
+
+    #[test]
+    fn letters_of_things_no_longer_carried_are_reused_after_26() {
+        let mut letters = InventoryLetters::default();
+        let all = names(26);
+        for name in all.iter() {
+            letters.letter_for(name, &all);
+        }
+        assert_eq!(letters.letter("Item 25"), Some('z'));
+
+        // Everything but the third and fifth items has been used up or dropped
+        let carried: Vec<String> = vec![all[2].clone(), all[4].clone(), "New".to_string()];
+        assert_eq!(letters.letter_for("New", &carried), Some('a'));
+        assert_eq!(letters.letter("Item 2"), Some('c'));
+        assert_eq!(letters.letter("Item 4"), Some('e'));
+        assert_eq!(letters.letter("Item 0"), None);
+        assert_eq!(letters.letter_for("Newer", &carried), Some('b'));
+    }
+
+    #[test]
+    fn no_letter_is_left_when_all_26_are_carried() {
+        let mut letters = InventoryLetters::default();
+        let mut all = names(27);
+        for name in all.iter() {
+            letters.letter_for(name, &all);
+        }
+        let last = all.pop().unwrap();
+        assert_eq!(letters.letter(&last), None);
+    }
+}
@@ -1,7 +1,13 @@
+use crate::combat::damage::DamageType;
+use crate::combat::dice;
+use crate::effects::status::StatusEffect;
+use crate::faction;
+use crate::items::equipment::EquipmentSlot;
 use cfglib;
 use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 use specs_derive::*;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use twyg::LoggerOpts;
@@ -20,6 +26,7 @@ pub struct Persistence {
 pub struct Game {
     pub title: String,
     pub welcome: String,
+    pub credits: String,
     pub persistence: Persistence,
 }
 
@@ -31,6 +38,8 @@ pub struct Tile {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Map {
+    pub width: i32,
+    pub height: i32,
     pub default: Tile,
     pub floor: Tile,
     pub wall: Tile,
@@ -61,6 +70,38 @@ pub struct Stats {
     pub starting_hp: i32,
     pub defense: i32,
     pub power: i32,
+    pub damage: dice::Dice,
+    #[serde(default)]
+    pub damage_type: DamageType,
+}
+
+/// Heals `amount` HP every `interval` turns.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Regeneration {
+    pub interval: i32,
+    pub amount: i32,
+}
+
+/// How much the player can carry: `base_capacity` plus `per_power` for
+/// each point of power. Past that they're burdened and every step costs
+/// `burdened_move_cost` more energy; past `overloaded_percent` of it they
+/// can't move at all, and won't pick anything up that would take them there.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Encumbrance {
+    pub base_capacity: i32,
+    pub per_power: i32,
+    pub overloaded_percent: i32,
+    pub burdened_move_cost: i32,
+}
+
+/// Anything in the pack can be thrown `base_range` tiles, plus one more for
+/// every `power_per_tile` points of power. Thrown items do a point of damage
+/// for every `weight_per_damage` they weigh.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Throwing {
+    pub base_range: i32,
+    pub power_per_tile: i32,
+    pub weight_per_damage: i32,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -71,6 +112,46 @@ pub struct Player {
     pub bg_color: (u8, u8, u8),
     pub view_range: ViewRange,
     pub stats: Stats,
+    pub speed: i32,
+    pub faction: String,
+    pub regeneration: Option<Regeneration>,
+    #[serde(default)]
+    pub resistances: Vec<DamageType>,
+    #[serde(default)]
+    pub vulnerabilities: Vec<DamageType>,
+}
+
+/// A class (or background) the player can pick when creating their
+/// character. The kit lists item keys from the items section.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Class {
+    pub name: String,
+    pub description: String,
+    pub stats: Stats,
+    pub view_range: ViewRange,
+    #[serde(default)]
+    pub kit: Vec<String>,
+    /// Replace the player's own resistances and vulnerabilities when given
+    pub resistances: Option<Vec<DamageType>>,
+    pub vulnerabilities: Option<Vec<DamageType>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Hunger {
+    pub well_fed: i32,
+    pub normal: i32,
+    pub hungry: i32,
+    pub weak: i32,
+    pub weak_penalty: i32,
+    pub starving_penalty: i32,
+    pub starving_damage: i32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Progression {
+    pub thresholds: Vec<i32>,
+    pub hp_per_level: i32,
+    pub stat_per_level: i32,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -78,6 +159,14 @@ pub struct Monster {
     pub name: String,
     pub chr: char,
     pub stats: Stats,
+    pub speed: i32,
+    pub faction: String,
+    pub xp: i32,
+    pub regeneration: Option<Regeneration>,
+    #[serde(default)]
+    pub resistances: Vec<DamageType>,
+    #[serde(default)]
+    pub vulnerabilities: Vec<DamageType>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -89,6 +178,39 @@ pub struct Monsters {
     pub view_range: ViewRange,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+pub struct Factions {
+    pub default_reaction: faction::Reaction,
+    pub reactions: HashMap<String, HashMap<String, faction::Reaction>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Combat {
+    pub to_hit_die: i32,
+    pub base_evasion: i32,
+    pub critical_multiplier: i32,
+    pub resistance_percent: i32,
+    pub vulnerability_percent: i32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ActionCosts {
+    pub movement: i32,
+    pub attack: i32,
+    pub use_item: i32,
+    pub pick_up: i32,
+    pub drop: i32,
+    pub equip: i32,
+    pub throw: i32,
+    pub wait: i32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Turns {
+    pub threshold: i32,
+    pub costs: ActionCosts,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize)]
 pub struct Rooms {
     pub max_count: i32,
@@ -108,6 +230,49 @@ pub struct TextArea {
     pub height: i32,
 }
 
+/// A panel down the right of the map listing what the player can see.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub struct Sidebar {
+    pub enabled: bool,
+    pub width: i32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MessageLog {
+    pub capacity: usize,
+    pub combat_color: (u8, u8, u8),
+    pub item_color: (u8, u8, u8),
+    pub system_color: (u8, u8, u8),
+    pub danger_color: (u8, u8, u8),
+    pub warning_color: (u8, u8, u8),
+    pub good_color: (u8, u8, u8),
+}
+
+/// Colours for the interface, by what they're used for.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub struct Theme {
+    pub title: (u8, u8, u8),
+    pub heading: (u8, u8, u8),
+    pub hint: (u8, u8, u8),
+    pub selected: (u8, u8, u8),
+    pub key: (u8, u8, u8),
+    pub muted: (u8, u8, u8),
+    pub status: (u8, u8, u8),
+    pub health: (u8, u8, u8),
+    pub experience: (u8, u8, u8),
+    pub effects: (u8, u8, u8),
+    pub good: (u8, u8, u8),
+    pub warning: (u8, u8, u8),
+    pub danger: (u8, u8, u8),
+    pub tooltip_fg: (u8, u8, u8),
+    pub tooltip_bg: (u8, u8, u8),
+    pub target_range: (u8, u8, u8),
+    pub target_path: (u8, u8, u8),
+    pub target_cursor: (u8, u8, u8),
+    pub target_invalid: (u8, u8, u8),
+    pub target_impact: (u8, u8, u8),
+}
+
 #[derive(Clone, Copy, Debug, Deserialize)]
 pub struct Gui {
     pub bg_color: (u8, u8, u8),
@@ -116,6 +281,21 @@ pub struct Gui {
     pub fullscreen: bool,
     pub map_area: MapArea,
     pub text_area: TextArea,
+    pub sidebar: Sidebar,
+    pub theme: Theme,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Equipment {
+    pub slot: EquipmentSlot,
+    #[serde(default)]
+    pub power: i32,
+    #[serde(default)]
+    pub defense: i32,
+    #[serde(default)]
+    pub absorb: i32,
+    pub damage: Option<dice::Dice>,
+    pub damage_type: Option<DamageType>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -124,30 +304,79 @@ pub struct Item {
     pub chr: char,
     pub fg_color: (u8, u8, u8),
     pub bg_color: (u8, u8, u8),
+    #[serde(default)]
     pub power: i32,
+    #[serde(default)]
+    pub weight: i32,
+    pub damage_type: Option<DamageType>,
     pub range: Option<i32>,
     pub radius: Option<i32>,
     pub duration: Option<i32>,
+    pub effect: Option<StatusEffect>,
+    pub nutrition: Option<i32>,
+    pub equipment: Option<Equipment>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct Items {
     pub health_potion: Item,
+    pub ration: Item,
+    pub haste_potion: Item,
     pub fireball_scroll: Item,
     pub magic_missile_scroll: Item,
     pub confusion_scroll: Item,
+    pub dagger: Item,
+    pub longsword: Item,
+    pub shield: Item,
+    pub leather_armour: Item,
+    pub helmet: Item,
+    pub ring_of_protection: Item,
 }
+
+impl Items {
+    /// Looks up an item template by its key in the config, e.g. "dagger".
+    pub fn get(&self, key: &str) -> Option<&Item> {
+        match key {
+            "health_potion" => Some(&self.health_potion),
+            "ration" => Some(&self.ration),
+            "haste_potion" => Some(&self.haste_potion),
+            "fireball_scroll" => Some(&self.fireball_scroll),
+            "magic_missile_scroll" => Some(&self.magic_missile_scroll),
+            "confusion_scroll" => Some(&self.confusion_scroll),
+            "dagger" => Some(&self.dagger),
+            "longsword" => Some(&self.longsword),
+            "shield" => Some(&self.shield),
+            "leather_armour" => Some(&self.leather_armour),
+            "helmet" => Some(&self.helmet),
+            "ring_of_protection" => Some(&self.ring_of_protection),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Component, Debug, Deserialize)]
 pub struct AppConfig {
     pub game: Game,
+    pub classes: Vec<Class>,
+    pub combat: Combat,
+    pub factions: Factions,
     pub map: Map,
+    pub message_log: MessageLog,
     pub gui: Gui,
+    pub hunger: Hunger,
+    pub encumbrance: Encumbrance,
+    /// Item keys for the starting hotbar slots, in order; only the first nine are used
+    #[serde(default)]
+    pub hotbar: Vec<String>,
     pub items: Items,
+    pub throwing: Throwing,
     pub logging: LoggerOpts,
     pub monsters: Monsters,
     pub npcs: NPCs,
     pub player: Player,
+    pub progression: Progression,
     pub rooms: Rooms,
+    pub turns: Turns,
 }
 
 impl AppConfig {
@@ -157,7 +386,7 @@ impl AppConfig {
                 c.game.create_savegame_dir();
                 c
             }
-            Err(_) => panic!("Configuration error: check the config file"),
+            Err(err) => panic!("Configuration error: {}", err),
         }
     }
 }
@@ -190,5 +419,13 @@ pub fn new_app_config() -> Result<AppConfig, cfglib::ConfigError> {
     c.merge(cfglib::File::with_name(CONFIG_FILE))?;
     // Merge in overrides from the environment
     c.merge(cfglib::Environment::with_prefix(ENV_PREFIX))?;
-    c.try_into()
+    let cfg: AppConfig = c.try_into()?;
+    // A 1 fumbles and the highest face crits, so they must be different faces
+    if cfg.combat.to_hit_die < 2 {
+        return Err(cfglib::ConfigError::Message(format!(
+            "combat.to_hit_die must be at least 2, not {}",
+            cfg.combat.to_hit_die
+        )));
+    }
+    Ok(cfg)
 }
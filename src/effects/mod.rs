@@ -0,0 +1,2 @@
+pub mod regeneration;
+pub mod status;
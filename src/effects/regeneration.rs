@@ -0,0 +1,47 @@
+use crate::components;
+use crate::game::turns;
+use specs::prelude::*;
+use specs::{self, Join, System};
+
+impl components::NaturalRegeneration {
+    pub fn new(interval: i32, amount: i32) -> Self {
+        components::NaturalRegeneration {
+            interval,
+            amount,
+            counter: 0,
+        }
+    }
+}
+
+/// Slowly heals anyone with natural regeneration, once every so many turns of
+/// the clock. The hungry don't heal.
+pub struct RegenerationSystem {}
+
+impl<'a> System<'a> for RegenerationSystem {
+    type SystemData = (
+        ReadExpect<'a, turns::Clock>,
+        WriteStorage<'a, components::NaturalRegeneration>,
+        WriteStorage<'a, components::CombatStats>,
+        ReadStorage<'a, components::Hunger>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (clock, mut regeneration, mut combat_stats, hunger) = data;
+
+        if !clock.advanced {
+            return;
+        }
+
+        for (regen, stats, hunger) in (&mut regeneration, &mut combat_stats, hunger.maybe()).join()
+        {
+            if hunger.map_or(false, |hunger| hunger.state.stops_healing()) {
+                continue;
+            }
+            regen.counter += 1;
+            if regen.counter >= regen.interval {
+                regen.counter = 0;
+                stats.hp = i32::min(stats.max_hp, stats.hp + regen.amount);
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use crate::combat::damage::DamageType;
+use crate::components;
+use crate::game;
+use crate::game::log::Category;
+use crate::game::turns;
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+use specs::{self, Join, System};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusKind {
+    Poison,
+    Burning,
+    Regeneration,
+    Haste,
+    Slow,
+    Blindness,
+    Paralysis,
+    Confusion,
+}
+
+/// What happens when an effect is applied to someone already suffering from
+/// it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stacking {
+    /// Magnitudes add up; the longer duration wins
+    Intensity,
+    /// Durations add up; the stronger magnitude wins
+    Duration,
+    /// Nothing adds up; the longer duration and stronger magnitude win
+    Refresh,
+}
+
+impl StatusKind {
+    pub fn stacking(self) -> Stacking {
+        match self {
+            StatusKind::Poison => Stacking::Intensity,
+            StatusKind::Blindness | StatusKind::Confusion => Stacking::Duration,
+            _ => Stacking::Refresh,
+        }
+    }
+
+    /// Haste and slow cancel each other out.
+    pub fn opposite(self) -> Option<StatusKind> {
+        match self {
+            StatusKind::Haste => Some(StatusKind::Slow),
+            StatusKind::Slow => Some(StatusKind::Haste),
+            _ => None,
+        }
+    }
+
+    /// How the effect reads when describing someone, e.g. "Goblin (poisoned)".
+    pub fn adjective(self) -> &'static str {
+        match self {
+            StatusKind::Poison => "poisoned",
+            StatusKind::Burning => "burning",
+            StatusKind::Regeneration => "regenerating",
+            StatusKind::Haste => "hasted",
+            StatusKind::Slow => "slowed",
+            StatusKind::Blindness => "blind",
+            StatusKind::Paralysis => "paralyzed",
+            StatusKind::Confusion => "confused",
+        }
+    }
+}
+
+impl fmt::Display for StatusKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            StatusKind::Poison => "poison",
+            StatusKind::Burning => "burning",
+            StatusKind::Regeneration => "regeneration",
+            StatusKind::Haste => "haste",
+            StatusKind::Slow => "slow",
+            StatusKind::Blindness => "blindness",
+            StatusKind::Paralysis => "paralysis",
+            StatusKind::Confusion => "confusion",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single effect with the number of turns it has left. The magnitude only
+/// matters for effects that do something every turn (damage or healing).
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub turns: i32,
+    #[serde(default)]
+    pub magnitude: i32,
+}
+
+impl components::StatusEffects {
+    pub fn get(&self, kind: StatusKind) -> Option<&StatusEffect> {
+        self.effects.iter().find(|e| e.kind == kind)
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    pub fn add(&mut self, effect: StatusEffect) {
+        if let Some(opposite) = effect.kind.opposite() {
+            if self.has(opposite) {
+                self.effects.retain(|e| e.kind != opposite);
+                return;
+            }
+        }
+        match self.effects.iter_mut().find(|e| e.kind == effect.kind) {
+            None => self.effects.push(effect),
+            Some(existing) => match effect.kind.stacking() {
+                Stacking::Intensity => {
+                    existing.magnitude += effect.magnitude;
+                    existing.turns = i32::max(existing.turns, effect.turns);
+                }
+                Stacking::Duration => {
+                    existing.turns += effect.turns;
+                    existing.magnitude = i32::max(existing.magnitude, effect.magnitude);
+                }
+                Stacking::Refresh => {
+                    existing.turns = i32::max(existing.turns, effect.turns);
+                    existing.magnitude = i32::max(existing.magnitude, effect.magnitude);
+                }
+            },
+        }
+    }
+
+    /// A comma-separated list of adjectives, e.g. "poisoned, slowed".
+    pub fn describe(&self) -> String {
+        self.effects
+            .iter()
+            .map(|e| e.kind.adjective())
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+
+    pub fn apply(
+        store: &mut WriteStorage<components::StatusEffects>,
+        target: Entity,
+        effect: StatusEffect,
+    ) {
+        if let Some(afflicted) = store.get_mut(target) {
+            afflicted.add(effect);
+        } else {
+            let mut afflicted = components::StatusEffects {
+                effects: Vec::new(),
+            };
+            afflicted.add(effect);
+            store
+                .insert(target, afflicted)
+                .expect("Unable to insert status effects");
+        }
+    }
+}
+
+/// Checks a single entity for an effect, for code that only has the world.
+pub fn has_effect(ecs: &World, entity: Entity, kind: StatusKind) -> bool {
+    let statuses = ecs.read_storage::<components::StatusEffects>();
+    match statuses.get(entity) {
+        None => false,
+        Some(statuses) => statuses.has(kind),
+    }
+}
+
+/// Applies damage-over-time and healing-over-time effects and counts every
+/// effect down, once per turn of the clock.
+pub struct StatusEffectSystem {}
+
+impl<'a> System<'a> for StatusEffectSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, turns::Clock>,
+        WriteExpect<'a, game::log::GameLog>,
+        WriteStorage<'a, components::StatusEffects>,
+        WriteStorage<'a, components::CombatStats>,
+        WriteStorage<'a, components::SufferDamage>,
+        WriteStorage<'a, components::Viewshed>,
+        ReadStorage<'a, components::AfflictedBy>,
+        WriteStorage<'a, components::LastHitBy>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            clock,
+            mut log,
+            mut statuses,
+            mut combat_stats,
+            mut suffer_damage,
+            mut viewsheds,
+            afflicted_by,
+            mut last_hit,
+        ) = data;
+
+        if !clock.advanced {
+            return;
+        }
+
+        let mut cured: Vec<Entity> = Vec::new();
+        for (entity, afflicted) in (&entities, &mut statuses).join() {
+            for effect in afflicted.effects.iter_mut() {
+                // Damage over time counts as a hit by whoever caused it
+                let harmful =
+                    effect.kind == StatusKind::Poison || effect.kind == StatusKind::Burning;
+                if let Some(source) = afflicted_by.get(entity).filter(|_| harmful) {
+                    last_hit
+                        .insert(
+                            entity,
+                            components::LastHitBy {
+                                attacker: source.attacker,
+                            },
+                        )
+                        .expect("Unable to insert last hit");
+                }
+                match effect.kind {
+                    StatusKind::Poison => components::SufferDamage::new_damage(
+                        &mut suffer_damage,
+                        entity,
+                        effect.magnitude,
+                        DamageType::Poison,
+                    ),
+                    StatusKind::Burning => components::SufferDamage::new_damage(
+                        &mut suffer_damage,
+                        entity,
+                        effect.magnitude,
+                        DamageType::Fire,
+                    ),
+                    StatusKind::Regeneration => {
+                        if let Some(stats) = combat_stats.get_mut(entity) {
+                            stats.hp = i32::min(stats.max_hp, stats.hp + effect.magnitude);
+                        }
+                    }
+                    _ => {}
+                }
+                effect.turns -= 1;
+            }
+
+            let had_blindness = afflicted.has(StatusKind::Blindness);
+            for effect in afflicted.effects.iter().filter(|e| e.turns < 1) {
+                if entity == *player_entity {
+                    log.add(
+                        Category::Combat,
+                        format!("You are no longer {}.", effect.kind.adjective()),
+                    );
+                }
+            }
+            afflicted.effects.retain(|e| e.turns > 0);
+
+            // Eyesight changes as soon as blindness comes or goes
+            if had_blindness {
+                if let Some(viewshed) = viewsheds.get_mut(entity) {
+                    viewshed.dirty = true;
+                }
+            }
+            if afflicted.effects.is_empty() {
+                cured.push(entity);
+            }
+        }
+
+        for entity in cured {
+            statuses.remove(entity);
+        }
+    }
+}
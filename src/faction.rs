@@ -0,0 +1,73 @@
+use crate::components;
+use crate::config;
+use crate::map;
+use rltk;
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Reaction {
+    Hostile,
+    Neutral,
+    Friendly,
+}
+
+/// Looks up how members of the faction `mine` react to members of the faction
+/// `theirs`. Members of the same faction are always friendly to each other;
+/// anything missing from the reaction table gets the configured default.
+pub fn reaction(cfg: &config::Factions, mine: &str, theirs: &str) -> Reaction {
+    if mine == theirs {
+        return Reaction::Friendly;
+    }
+    match cfg.reactions.get(mine).and_then(|r| r.get(theirs)) {
+        Some(reaction) => *reaction,
+        None => cfg.default_reaction,
+    }
+}
+
+/// Same as `reaction`, but for the faction components of two entities, either
+/// of which may not belong to any faction at all.
+pub fn between(
+    cfg: &config::Factions,
+    mine: Option<&components::Faction>,
+    theirs: Option<&components::Faction>,
+) -> Reaction {
+    match (mine, theirs) {
+        (Some(mine), Some(theirs)) => reaction(cfg, &mine.name, &theirs.name),
+        _ => cfg.default_reaction,
+    }
+}
+
+/// Everything with combat stats that the viewer can see and is hostile
+/// towards, nearest first.
+pub fn visible_hostiles(ecs: &World, viewer: Entity) -> Vec<Entity> {
+    let cfg = ecs.fetch::<config::AppConfig>();
+    let game_map = ecs.fetch::<map::Map>();
+    let viewsheds = ecs.read_storage::<components::Viewshed>();
+    let positions = ecs.read_storage::<components::Position>();
+    let factions = ecs.read_storage::<components::Faction>();
+    let combat_stats = ecs.read_storage::<components::CombatStats>();
+
+    let (viewshed, pos) = match (viewsheds.get(viewer), positions.get(viewer)) {
+        (Some(viewshed), Some(pos)) => (viewshed, pos),
+        _ => return Vec::new(),
+    };
+    let here = rltk::Point::new(pos.x, pos.y);
+    let mut hostiles: Vec<(Entity, f32)> = Vec::new();
+    for tile in viewshed.visible_tiles.iter() {
+        let idx = game_map.xy_idx(tile.x, tile.y);
+        for other in game_map.tile_content[idx].iter() {
+            if *other == viewer || combat_stats.get(*other).is_none() {
+                continue;
+            }
+            let reaction = between(&cfg.factions, factions.get(viewer), factions.get(*other));
+            if reaction == Reaction::Hostile {
+                let distance = rltk::DistanceAlg::Pythagoras.distance2d(here, *tile);
+                hostiles.push((*other, distance));
+            }
+        }
+    }
+    hostiles.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    hostiles.into_iter().map(|(entity, _)| entity).collect()
+}
@@ -1,3 +1,138 @@
+use crate::config;
+use std::collections::VecDeque;
+use std::fmt;
+
+pub type Color = (u8, u8, u8);
+
+/// What a message is about, so the history can be filtered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Category {
+    Combat,
+    Item,
+    System,
+}
+
+impl Category {
+    pub fn all() -> [Category; 3] {
+        [Category::Combat, Category::Item, Category::System]
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Category::Combat => "Combat",
+            Category::Item => "Items",
+            Category::System => "System",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Ways of making part of a message stand out, each with its own colour in
+/// the config.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Highlight {
+    Danger,
+    Warning,
+    Good,
+}
+
+/// A run of text, highlighted or not. A span without a highlight takes its
+/// category's colour.
+#[derive(Clone, Debug)]
+pub struct Span {
+    pub text: String,
+    pub highlight: Option<Highlight>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub turn: i32,
+    pub category: Category,
+    pub spans: Vec<Span>,
+}
+
+impl LogEntry {
+    pub fn new(category: Category) -> Self {
+        LogEntry {
+            turn: 0,
+            category,
+            spans: Vec::new(),
+        }
+    }
+
+    pub fn text<S: ToString>(mut self, text: S) -> Self {
+        self.spans.push(Span {
+            text: text.to_string(),
+            highlight: None,
+        });
+        self
+    }
+
+    pub fn highlighted<S: ToString>(mut self, text: S, highlight: Highlight) -> Self {
+        self.spans.push(Span {
+            text: text.to_string(),
+            highlight: Some(highlight),
+        });
+        self
+    }
+
+    /// The message without its colours.
+    pub fn plain(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+
+/// The messages shown to the player. Only the most recent ones are kept;
+/// each is stamped with the turn it was logged on.
 pub struct GameLog {
-    pub entries: Vec<String>,
+    entries: VecDeque<LogEntry>,
+    pub turn: i32,
+    cfg: config::MessageLog,
+}
+
+impl GameLog {
+    pub fn new(cfg: &config::MessageLog) -> Self {
+        GameLog {
+            entries: VecDeque::new(),
+            turn: 0,
+            cfg: cfg.clone(),
+        }
+    }
+
+    pub fn push(&mut self, mut entry: LogEntry) {
+        entry.turn = self.turn;
+        self.entries.push_back(entry);
+        while self.entries.len() > self.cfg.capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Logs a message in its category's colour.
+    pub fn add<S: ToString>(&mut self, category: Category, message: S) {
+        self.push(LogEntry::new(category).text(message));
+    }
+
+    /// Oldest first.
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn color(&self, category: Category) -> Color {
+        match category {
+            Category::Combat => self.cfg.combat_color,
+            Category::Item => self.cfg.item_color,
+            Category::System => self.cfg.system_color,
+        }
+    }
+
+    pub fn span_color(&self, entry: &LogEntry, span: &Span) -> Color {
+        match span.highlight {
+            None => self.color(entry.category),
+            Some(Highlight::Danger) => self.cfg.danger_color,
+            Some(Highlight::Warning) => self.cfg.warning_color,
+            Some(Highlight::Good) => self.cfg.good_color,
+        }
+    }
 }
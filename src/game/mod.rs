@@ -1,4 +1,6 @@
 pub mod log;
 pub mod persistence;
 pub mod state;
+pub mod systems;
+pub mod turns;
 pub mod world;
@@ -1,17 +1,18 @@
-use crate::ai::monster;
 use crate::combat;
 use crate::components;
 use crate::config;
 use crate::game;
+use crate::game::log::Category;
 use crate::game::persistence;
+use crate::game::systems;
+use crate::game::turns;
 use crate::gui;
 use crate::gui::menus;
 use crate::map;
-use crate::physics;
 use crate::player;
 use crate::rooms;
 use log;
-use rltk::{self, Console, GameState};
+use rltk::{self, Console, GameState, Point};
 use specs::prelude::*;
 
 #[derive(PartialEq, Copy, Clone)]
@@ -20,14 +21,31 @@ pub enum RunState {
     PreRun,
     PlayerTurn,
     MonsterTurn,
-    Paused,
     Quitting,
+    ShowCharacter,
+    ShowHelp,
     ShowDropItem,
+    ShowThrowItem,
+    ShowEquipment,
     ShowInventory,
+    ShowHotbarItem,
+    ChooseHotbarSlot {
+        item: Entity,
+    },
+    LevelUp,
+    Looking,
+    ShowLog {
+        scroll: usize,
+        filter: Option<Category>,
+    },
     ShowTargeting {
         range: i32,
         item: Entity,
     },
+    ShowThrowTarget {
+        range: i32,
+        item: Entity,
+    },
     NextLevel,
     ShowMainMenu,
     MainMenu {
@@ -37,38 +55,34 @@ pub enum RunState {
     SaveGame,
     LoadGame,
     ShowCredits,
+    CreateName,
+    CreateClass {
+        selection: usize,
+    },
 }
 
 pub struct State {
     pub ecs: specs::World,
+    pub systems: systems::Systems,
 }
 
 impl State {
-    fn run_systems(&mut self) {
-        let mut vis = physics::VisibilitySystem {};
-        vis.run_now(&self.ecs);
-        let mut mob = monster::MonsterAI {};
-        mob.run_now(&self.ecs);
-        let mut mapindex = map::IndexingSystem {};
-        mapindex.run_now(&self.ecs);
-        let mut melee = combat::melee::MeleeSystem {};
-        melee.run_now(&self.ecs);
-        let mut damage = combat::damage::DamageSystem {};
-        damage.run_now(&self.ecs);
-        let mut pickup = player::inventory::ItemCollectionSystem {};
-        pickup.run_now(&self.ecs);
-        let mut itemuse = player::inventory::ItemUseSystem {};
-        itemuse.run_now(&self.ecs);
-        let mut drop_items = player::inventory::ItemDropSystem {};
-        drop_items.run_now(&self.ecs);
+    pub fn new() -> Self {
+        State {
+            ecs: specs::World::new(),
+            systems: systems::Systems::new(),
+        }
+    }
 
-        self.ecs.maintain();
+    fn run_systems(&mut self, phase: systems::Phase) {
+        self.systems.run(phase, &mut self.ecs);
     }
 
     fn entities_to_remove_on_level_change(&mut self) -> Vec<Entity> {
         let entities = self.ecs.entities();
         let player = self.ecs.read_storage::<components::Player>();
         let backpack = self.ecs.read_storage::<components::InBackpack>();
+        let equipped = self.ecs.read_storage::<components::Equipped>();
         let player_entity = self.ecs.fetch::<Entity>();
 
         let mut to_delete: Vec<Entity> = Vec::new();
@@ -88,6 +102,12 @@ impl State {
                     should_delete = false;
                 }
             }
+            let eq = equipped.get(entity);
+            if let Some(eq) = eq {
+                if eq.owner == *player_entity {
+                    should_delete = false;
+                }
+            }
 
             if should_delete {
                 to_delete.push(entity);
@@ -144,9 +164,10 @@ impl State {
 
         // Notify the player and give them some health
         let mut gamelog = self.ecs.fetch_mut::<game::log::GameLog>();
-        gamelog
-            .entries
-            .push("You descend to the next level, and take a moment to heal.".to_string());
+        gamelog.add(
+            Category::System,
+            "You descend to the next level, and take a moment to heal.",
+        );
         let mut player_health_store = self.ecs.write_storage::<components::CombatStats>();
         let player_health = player_health_store.get_mut(character.entity);
         if let Some(player_health) = player_health {
@@ -166,7 +187,11 @@ impl GameState for State {
         ctx.cls();
 
         match newrunstate {
-            RunState::MainMenu { .. } => {}
+            RunState::MainMenu { .. }
+            | RunState::ShowCredits
+            | RunState::CreateName
+            | RunState::CreateClass { .. }
+            | RunState::ShowLog { .. } => {}
             _ => {
                 map::draw(&self.ecs, ctx);
 
@@ -174,13 +199,17 @@ impl GameState for State {
                     let positions = self.ecs.read_storage::<components::Position>();
                     let renderables = self.ecs.read_storage::<components::Renderable>();
                     let game_map = self.ecs.fetch::<map::Map>();
+                    let camera = gui::camera::Camera::new(&self.ecs);
 
                     let mut data = (&positions, &renderables).join().collect::<Vec<_>>();
                     data.sort_by(|&a, &b| b.1.render_order.cmp(&a.1.render_order));
                     for (pos, render) in data.iter() {
                         let idx = game_map.xy_idx(pos.x, pos.y);
-                        if game_map.visible_tiles[idx] {
-                            ctx.set(pos.x, pos.y, render.fg, render.bg, render.glyph)
+                        if !game_map.visible_tiles[idx] {
+                            continue;
+                        }
+                        if let Some(screen) = camera.to_screen(Point::new(pos.x, pos.y)) {
+                            ctx.set(screen.x, screen.y, render.fg, render.bg, render.glyph)
                         }
                     }
 
@@ -190,88 +219,241 @@ impl GameState for State {
         }
 
         match newrunstate {
+            RunState::CreateName => {
+                if menus::creation::name(self, ctx) == menus::item::Result::Selected {
+                    player::creation::rename(&self.ecs);
+                    newrunstate = RunState::CreateClass { selection: 0 };
+                }
+            }
+            RunState::CreateClass { selection } => {
+                let classes = self.ecs.fetch::<config::AppConfig>().classes.clone();
+                if classes.is_empty() {
+                    newrunstate = RunState::PreRun;
+                } else {
+                    match menus::creation::class(self, ctx, selection) {
+                        menus::creation::Result::NoSelection { selected } => {
+                            newrunstate = RunState::CreateClass {
+                                selection: selected,
+                            }
+                        }
+                        menus::creation::Result::Back => newrunstate = RunState::CreateName,
+                        menus::creation::Result::Selected { selected } => {
+                            player::creation::apply_class(&mut self.ecs, &classes[selected]);
+                            newrunstate = RunState::PreRun;
+                        }
+                    }
+                }
+            }
             RunState::PreRun => {
-                self.run_systems();
-                self.ecs.maintain();
+                self.run_systems(systems::Phase::PreRun);
                 newrunstate = RunState::AwaitingInput;
             }
             RunState::AwaitingInput => {
-                newrunstate = player::user::input(self, ctx);
+                let player_entity = *self.ecs.fetch::<Entity>();
+                if player::progression::has_unspent_levels(&self.ecs, player_entity) {
+                    newrunstate = RunState::LevelUp;
+                } else if player::activity::is_active(&self.ecs) {
+                    // Any key stops whatever the player is busy doing
+                    if ctx.key.is_some() {
+                        player::activity::stop(&self.ecs, "You stop.");
+                    } else if let Some(action) = player::activity::step(&mut self.ecs) {
+                        turns::spend(&self.ecs, player_entity, action);
+                        newrunstate = RunState::PlayerTurn;
+                    }
+                } else {
+                    newrunstate = player::user::input(self, ctx);
+                }
+            }
+            RunState::LevelUp => {
+                let result = menus::level_up::show(self, ctx);
+                if let (menus::item::Result::Selected, Some(stat)) = result {
+                    let player_entity = *self.ecs.fetch::<Entity>();
+                    player::progression::raise(&self.ecs, player_entity, stat);
+                    newrunstate = RunState::AwaitingInput;
+                }
             }
             RunState::PlayerTurn => {
-                self.run_systems();
-                self.ecs.maintain();
+                self.run_systems(systems::Phase::PlayerTurn);
                 newrunstate = RunState::MonsterTurn;
             }
             RunState::MonsterTurn => {
-                self.run_systems();
-                self.ecs.maintain();
+                // Let the clock run (and everyone else act) until the player
+                // has built up enough energy for another action
+                let player_entity = *self.ecs.fetch::<Entity>();
+                while !turns::can_act(&self.ecs, player_entity) {
+                    self.run_systems(systems::Phase::MonsterTurn);
+                    combat::damage::delete_the_dead(&mut self.ecs);
+                }
                 newrunstate = RunState::AwaitingInput;
             }
             RunState::ShowInventory => {
                 let result = menus::item::show_inventory(self, ctx);
+                match result.0 {
+                    menus::item::Result::Cancel => newrunstate = RunState::AwaitingInput,
+                    menus::item::Result::NoResponse => {}
+                    menus::item::Result::Selected => {
+                        newrunstate = player::character::use_item(&self.ecs, result.1.unwrap());
+                    }
+                }
+            }
+            RunState::ShowHotbarItem => {
+                let result = menus::item::hotbar(self, ctx);
+                match result.0 {
+                    menus::item::Result::Cancel => newrunstate = RunState::AwaitingInput,
+                    menus::item::Result::NoResponse => {}
+                    menus::item::Result::Selected => {
+                        newrunstate = RunState::ChooseHotbarSlot {
+                            item: result.1.unwrap(),
+                        };
+                    }
+                }
+            }
+            RunState::ChooseHotbarSlot { item } => match menus::hotbar::slot(self, ctx, item) {
+                menus::hotbar::Result::Cancel => newrunstate = RunState::AwaitingInput,
+                menus::hotbar::Result::NoResponse => {}
+                menus::hotbar::Result::Selected(slot) => {
+                    player::hotbar::assign(&self.ecs, slot, item);
+                    newrunstate = RunState::AwaitingInput;
+                }
+            },
+            RunState::ShowDropItem => {
+                let result = menus::item::drop(self, ctx);
                 match result.0 {
                     menus::item::Result::Cancel => newrunstate = RunState::AwaitingInput,
                     menus::item::Result::NoResponse => {}
                     menus::item::Result::Selected => {
                         let item_entity = result.1.unwrap();
-                        let is_ranged = self.ecs.read_storage::<components::Ranged>();
-                        let is_item_ranged = is_ranged.get(item_entity);
-                        if let Some(is_item_ranged) = is_item_ranged {
-                            newrunstate = RunState::ShowTargeting {
-                                range: is_item_ranged.range,
-                                item: item_entity,
-                            };
-                        } else {
-                            let mut intent = self.ecs.write_storage::<components::WantsToUseItem>();
+                        let player_entity = *self.ecs.fetch::<Entity>();
+                        {
+                            let mut intent =
+                                self.ecs.write_storage::<components::WantsToDropItem>();
                             intent
                                 .insert(
-                                    *self.ecs.fetch::<Entity>(),
-                                    components::WantsToUseItem {
-                                        item: item_entity,
-                                        target: None,
-                                    },
+                                    player_entity,
+                                    components::WantsToDropItem { item: item_entity },
                                 )
                                 .expect("Unable to insert intent");
-                            newrunstate = RunState::PlayerTurn;
                         }
+                        turns::spend(&self.ecs, player_entity, turns::Action::Drop);
+                        newrunstate = RunState::PlayerTurn;
                     }
                 }
             }
-            RunState::ShowDropItem => {
-                let result = menus::item::drop(self, ctx);
+            RunState::ShowThrowItem => {
+                let result = menus::item::throw(self, ctx);
+                match result.0 {
+                    menus::item::Result::Cancel => newrunstate = RunState::AwaitingInput,
+                    menus::item::Result::NoResponse => {}
+                    menus::item::Result::Selected => {
+                        let player_entity = *self.ecs.fetch::<Entity>();
+                        let range = player::inventory::throw_range(&self.ecs, player_entity);
+                        menus::target::begin(&self.ecs, range);
+                        newrunstate = RunState::ShowThrowTarget {
+                            range,
+                            item: result.1.unwrap(),
+                        };
+                    }
+                }
+            }
+            RunState::ShowCharacter => {
+                if menus::character::show(self, ctx) == menus::item::Result::Cancel {
+                    newrunstate = RunState::AwaitingInput;
+                }
+            }
+            RunState::ShowHelp => {
+                if menus::help::show(self, ctx) == menus::item::Result::Cancel {
+                    newrunstate = RunState::AwaitingInput;
+                }
+            }
+            RunState::ShowEquipment => {
+                let result = menus::equipment::show(self, ctx);
                 match result.0 {
                     menus::item::Result::Cancel => newrunstate = RunState::AwaitingInput,
                     menus::item::Result::NoResponse => {}
                     menus::item::Result::Selected => {
                         let item_entity = result.1.unwrap();
-                        let mut intent = self.ecs.write_storage::<components::WantsToDropItem>();
-                        intent
-                            .insert(
-                                *self.ecs.fetch::<Entity>(),
-                                components::WantsToDropItem { item: item_entity },
-                            )
-                            .expect("Unable to insert intent");
+                        let player_entity = *self.ecs.fetch::<Entity>();
+                        {
+                            let mut intent =
+                                self.ecs.write_storage::<components::WantsToRemoveItem>();
+                            intent
+                                .insert(
+                                    player_entity,
+                                    components::WantsToRemoveItem { item: item_entity },
+                                )
+                                .expect("Unable to insert intent");
+                        }
+                        turns::spend(&self.ecs, player_entity, turns::Action::Equip);
                         newrunstate = RunState::PlayerTurn;
                     }
                 }
             }
+            RunState::Looking => {
+                if menus::look::show(self, ctx) == menus::item::Result::Cancel {
+                    newrunstate = RunState::AwaitingInput;
+                }
+            }
+            RunState::ShowLog { scroll, filter } => {
+                newrunstate = match menus::history::show(self, ctx, scroll, filter) {
+                    menus::history::Result::Close => RunState::AwaitingInput,
+                    menus::history::Result::Browse { scroll, filter } => {
+                        RunState::ShowLog { scroll, filter }
+                    }
+                };
+            }
             RunState::ShowTargeting { range, item } => {
-                let result = menus::target::ranged(self, ctx, range);
+                let preview = match self
+                    .ecs
+                    .read_storage::<components::AreaOfEffect>()
+                    .get(item)
+                {
+                    Some(_) => menus::target::Preview::None,
+                    None => menus::target::Preview::Bolt,
+                };
+                let result = menus::target::ranged(self, ctx, range, preview);
+                match result.0 {
+                    menus::item::Result::Cancel => newrunstate = RunState::AwaitingInput,
+                    menus::item::Result::NoResponse => {}
+                    menus::item::Result::Selected => {
+                        let player_entity = *self.ecs.fetch::<Entity>();
+                        {
+                            let mut intent = self.ecs.write_storage::<components::WantsToUseItem>();
+                            intent
+                                .insert(
+                                    player_entity,
+                                    components::WantsToUseItem {
+                                        item,
+                                        target: result.1,
+                                    },
+                                )
+                                .expect("Unable to insert intent");
+                        }
+                        turns::spend(&self.ecs, player_entity, turns::Action::UseItem);
+                        newrunstate = RunState::PlayerTurn;
+                    }
+                }
+            }
+            RunState::ShowThrowTarget { range, item } => {
+                let result = menus::target::ranged(self, ctx, range, menus::target::Preview::Throw);
                 match result.0 {
                     menus::item::Result::Cancel => newrunstate = RunState::AwaitingInput,
                     menus::item::Result::NoResponse => {}
                     menus::item::Result::Selected => {
-                        let mut intent = self.ecs.write_storage::<components::WantsToUseItem>();
-                        intent
-                            .insert(
-                                *self.ecs.fetch::<Entity>(),
-                                components::WantsToUseItem {
-                                    item,
-                                    target: result.1,
-                                },
-                            )
-                            .expect("Unable to insert intent");
+                        let player_entity = *self.ecs.fetch::<Entity>();
+                        {
+                            let mut intent =
+                                self.ecs.write_storage::<components::WantsToThrowItem>();
+                            intent
+                                .insert(
+                                    player_entity,
+                                    components::WantsToThrowItem {
+                                        item,
+                                        target: result.1.unwrap(),
+                                    },
+                                )
+                                .expect("Unable to insert intent");
+                        }
+                        turns::spend(&self.ecs, player_entity, turns::Action::Throw);
                         newrunstate = RunState::PlayerTurn;
                     }
                 }
@@ -299,6 +481,9 @@ impl GameState for State {
                             menus::main::Selection::ContinuePlaying => {
                                 newrunstate = RunState::AwaitingInput
                             }
+                            menus::main::Selection::Character => {
+                                newrunstate = RunState::ShowCharacter
+                            }
                             menus::main::Selection::NewGame => newrunstate = RunState::StartNewGame,
                             menus::main::Selection::SaveGame => newrunstate = RunState::SaveGame,
                             menus::main::Selection::LoadGame => newrunstate = RunState::LoadGame,
@@ -340,12 +525,11 @@ impl GameState for State {
                 log::info!("Game loaded.");
             }
             RunState::ShowCredits => {
-                log::info!("Credits screen not yet implemented");
-                newrunstate = RunState::AwaitingInput;
-            }
-            RunState::Paused => {
-                self.run_systems();
-                newrunstate = RunState::AwaitingInput;
+                if menus::credits::show(self, ctx) == menus::item::Result::Cancel {
+                    newrunstate = RunState::MainMenu {
+                        menu_selection: menus::main::Selection::Credits,
+                    };
+                }
             }
             RunState::Quitting => {
                 log::info!("Quitting ...");
@@ -0,0 +1,109 @@
+use crate::ai::monster;
+use crate::combat;
+use crate::effects::regeneration;
+use crate::effects::status;
+use crate::game::turns;
+use crate::map;
+use crate::physics;
+use crate::player;
+use specs::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    PreRun,
+    PlayerTurn,
+    MonsterTurn,
+}
+
+/// One dispatcher per turn phase, so that every system only runs when it has
+/// something to do. Systems that don't share any data are run in parallel.
+pub struct Systems {
+    pre_run: Dispatcher<'static, 'static>,
+    player_turn: Dispatcher<'static, 'static>,
+    monster_turn: Dispatcher<'static, 'static>,
+}
+
+impl Systems {
+    pub fn new() -> Self {
+        Systems {
+            pre_run: pre_run(),
+            player_turn: player_turn(),
+            monster_turn: monster_turn(),
+        }
+    }
+
+    pub fn run(&mut self, phase: Phase, ecs: &mut World) {
+        match phase {
+            Phase::PreRun => self.pre_run.dispatch(ecs),
+            Phase::PlayerTurn => self.player_turn.dispatch(ecs),
+            Phase::MonsterTurn => self.monster_turn.dispatch(ecs),
+        }
+        ecs.maintain();
+    }
+}
+
+impl Default for Systems {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds the map index and everyone's field of view before the first turn.
+fn pre_run() -> Dispatcher<'static, 'static> {
+    DispatcherBuilder::new()
+        .with(map::IndexingSystem {}, "map_index", &[])
+        .with(physics::VisibilitySystem {}, "visibility", &["map_index"])
+        .build()
+}
+
+/// Resolves whatever the player asked for: attacks, item handling and the
+/// view from wherever they ended up.
+fn player_turn() -> Dispatcher<'static, 'static> {
+    DispatcherBuilder::new()
+        .with(physics::VisibilitySystem {}, "visibility", &[])
+        .with(combat::melee::MeleeSystem {}, "melee", &[])
+        .with(player::inventory::ItemCollectionSystem {}, "pickup", &[])
+        .with(player::inventory::ThrowSystem {}, "throw", &[])
+        .with(player::inventory::ItemUseSystem {}, "item_use", &[])
+        .with(player::inventory::ItemDropSystem {}, "drop", &["item_use"])
+        .with(player::inventory::ItemEquipSystem {}, "equip", &[])
+        .with(
+            player::inventory::ItemRemoveSystem {},
+            "unequip",
+            &["equip"],
+        )
+        .with(
+            combat::damage::DamageSystem {},
+            "damage",
+            &["melee", "throw", "item_use", "equip", "unequip"],
+        )
+        .with(
+            map::IndexingSystem {},
+            "map_index",
+            &["pickup", "drop", "throw"],
+        )
+        .build()
+}
+
+/// Advances the clock and lets every actor with enough energy take a turn.
+fn monster_turn() -> Dispatcher<'static, 'static> {
+    DispatcherBuilder::new()
+        .with(turns::ClockSystem {}, "clock", &[])
+        .with(status::StatusEffectSystem {}, "status_effects", &["clock"])
+        .with(
+            regeneration::RegenerationSystem {},
+            "regeneration",
+            &["status_effects"],
+        )
+        .with(player::hunger::HungerSystem {}, "hunger", &["clock"])
+        .with(monster::MonsterAI {}, "monster_ai", &["status_effects"])
+        .with(combat::melee::MeleeSystem {}, "melee", &["monster_ai"])
+        .with(
+            combat::damage::DamageSystem {},
+            "damage",
+            &["melee", "status_effects", "hunger"],
+        )
+        .with(map::IndexingSystem {}, "map_index", &["monster_ai"])
+        .with(physics::VisibilitySystem {}, "visibility", &["map_index"])
+        .build()
+}
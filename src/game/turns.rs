@@ -0,0 +1,122 @@
+use crate::components;
+use crate::config;
+use crate::effects::status::StatusKind;
+use crate::game;
+use crate::player::encumbrance;
+use specs::prelude::*;
+use specs::{self, Join, System};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Move,
+    Attack,
+    UseItem,
+    PickUp,
+    Drop,
+    Equip,
+    Throw,
+    Wait,
+}
+
+impl Action {
+    pub fn cost(self, cfg: &config::ActionCosts) -> i32 {
+        match self {
+            Action::Move => cfg.movement,
+            Action::Attack => cfg.attack,
+            Action::UseItem => cfg.use_item,
+            Action::PickUp => cfg.pick_up,
+            Action::Drop => cfg.drop,
+            Action::Equip => cfg.equip,
+            Action::Throw => cfg.throw,
+            Action::Wait => cfg.wait,
+        }
+    }
+}
+
+/// Counts how often the clock has advanced; an actor of speed 100 gets one
+/// action per turn with the default threshold. `advanced` tells the systems
+/// running after the clock whether a new turn has just begun.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Clock {
+    pub turn: i32,
+    pub advanced: bool,
+}
+
+impl components::Energy {
+    pub fn can_act(&self, cfg: &config::Turns) -> bool {
+        self.current >= cfg.threshold
+    }
+
+    pub fn spend(&mut self, action: Action, cfg: &config::Turns) {
+        self.current -= action.cost(&cfg.costs);
+    }
+}
+
+/// Hands out energy to every actor, but only once nobody has enough left to
+/// act; that way everyone gets to use up what they have before time moves on.
+pub struct ClockSystem {}
+
+impl<'a> System<'a> for ClockSystem {
+    type SystemData = (
+        ReadExpect<'a, config::AppConfig>,
+        WriteExpect<'a, Clock>,
+        WriteExpect<'a, game::log::GameLog>,
+        WriteStorage<'a, components::Energy>,
+        ReadStorage<'a, components::StatusEffects>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (cfg, mut clock, mut log, mut energies, statuses) = data;
+
+        clock.advanced = false;
+        if energies.join().any(|energy| energy.can_act(&cfg.turns)) {
+            return;
+        }
+
+        // Everyone gets at least a trickle, so nobody can be stalled forever
+        for (energy, afflicted) in (&mut energies, statuses.maybe()).join() {
+            let mut speed = energy.speed;
+            if let Some(afflicted) = afflicted {
+                if afflicted.has(StatusKind::Haste) {
+                    speed *= 2;
+                }
+                if afflicted.has(StatusKind::Slow) {
+                    speed /= 2;
+                }
+            }
+            energy.current += i32::max(1, speed);
+        }
+        clock.turn += 1;
+        clock.advanced = true;
+        log.turn = clock.turn;
+    }
+}
+
+/// Charges an entity for the action it just took. Entities without energy
+/// (e.g., ones that were never given a speed) act for free.
+pub fn spend(ecs: &World, entity: Entity, action: Action) {
+    let cfg = ecs.fetch::<config::AppConfig>();
+    // Every step is harder going under a heavy load
+    let penalty = match (action, encumbrance::load(ecs, entity)) {
+        (Action::Move, Some(load)) => load.encumbrance.move_penalty(&cfg.encumbrance),
+        _ => 0,
+    };
+    let mut energies = ecs.write_storage::<components::Energy>();
+    if let Some(energy) = energies.get_mut(entity) {
+        energy.spend(action, &cfg.turns);
+        energy.current -= penalty;
+    }
+    let mut statistics = ecs.write_storage::<components::Statistics>();
+    if let Some(statistics) = statistics.get_mut(entity) {
+        statistics.turns_taken += 1;
+    }
+}
+
+pub fn can_act(ecs: &World, entity: Entity) -> bool {
+    let cfg = ecs.fetch::<config::AppConfig>();
+    let energies = ecs.read_storage::<components::Energy>();
+    match energies.get(entity) {
+        None => true,
+        Some(energy) => energy.can_act(&cfg.turns),
+    }
+}
@@ -1,6 +1,7 @@
 use crate::components;
 use crate::config;
 use crate::game;
+use crate::gui;
 use crate::map;
 use crate::player;
 use log;
@@ -16,9 +17,11 @@ pub fn setup(cfg: config::AppConfig, gs: &mut game::state::State) {
     log::info!("Starting world setup ...");
     let title = cfg.game.title.clone();
     log::debug!("Setting up game log ...");
-    let game_log = game::log::GameLog {
-        entries: vec![format!("{} {}", cfg.game.welcome.clone(), title)],
-    };
+    let mut game_log = game::log::GameLog::new(&cfg.message_log);
+    game_log.add(
+        game::log::Category::System,
+        format!("{} {}", cfg.game.welcome.clone(), title),
+    );
 
     log::debug!("Registering components ...");
     gs.ecs.register::<components::Position>();
@@ -28,20 +31,42 @@ pub fn setup(cfg: config::AppConfig, gs: &mut game::state::State) {
     gs.ecs.register::<components::Monster>();
     gs.ecs.register::<components::Name>();
     gs.ecs.register::<components::BlocksTile>();
+    gs.ecs.register::<components::Faction>();
     gs.ecs.register::<components::CombatStats>();
+    gs.ecs.register::<components::Energy>();
+    gs.ecs.register::<components::Resistant>();
+    gs.ecs.register::<components::Vulnerable>();
     gs.ecs.register::<components::WantsToMelee>();
     gs.ecs.register::<components::SufferDamage>();
+    gs.ecs.register::<components::LastHitBy>();
+    gs.ecs.register::<components::AfflictedBy>();
+    gs.ecs.register::<components::Experience>();
+    gs.ecs.register::<components::ExperienceValue>();
+    gs.ecs.register::<components::Statistics>();
+    gs.ecs.register::<components::Hunger>();
+    gs.ecs.register::<components::NaturalRegeneration>();
+    gs.ecs.register::<components::ProvidesFood>();
     gs.ecs.register::<components::Item>();
     gs.ecs.register::<components::ProvidesHealing>();
     gs.ecs.register::<components::InflictsDamage>();
     gs.ecs.register::<components::AreaOfEffect>();
     gs.ecs.register::<components::Consumable>();
+    gs.ecs.register::<components::Weight>();
     gs.ecs.register::<components::Ranged>();
     gs.ecs.register::<components::InBackpack>();
+    gs.ecs.register::<components::InventoryLetters>();
+    gs.ecs.register::<components::Hotbar>();
     gs.ecs.register::<components::WantsToPickupItem>();
     gs.ecs.register::<components::WantsToUseItem>();
     gs.ecs.register::<components::WantsToDropItem>();
-    gs.ecs.register::<components::Confusion>();
+    gs.ecs.register::<components::WantsToThrowItem>();
+    gs.ecs.register::<components::Equippable>();
+    gs.ecs.register::<components::Equipped>();
+    gs.ecs.register::<components::EquipmentBonus>();
+    gs.ecs.register::<components::WantsToEquipItem>();
+    gs.ecs.register::<components::WantsToRemoveItem>();
+    gs.ecs.register::<components::InflictsStatus>();
+    gs.ecs.register::<components::StatusEffects>();
     gs.ecs.register::<SimpleMarker<components::SerializeMe>>();
     gs.ecs.register::<components::SerializationHelper>();
 
@@ -57,7 +82,11 @@ pub fn setup(cfg: config::AppConfig, gs: &mut game::state::State) {
     log::info!("Completed map setup");
 
     log::debug!("Inserting configurations into component system ...");
+    gs.ecs
+        .insert(gui::menus::credits::Credits::load(&cfg.game.credits));
     gs.ecs.insert(cfg);
+    log::debug!("Inserting game clock into component system ...");
+    gs.ecs.insert(game::turns::Clock::default());
     log::debug!("Inserting game log into component system ...");
     gs.ecs.insert(game_log);
     log::debug!("Inserting map into component system ...");
@@ -65,8 +94,11 @@ pub fn setup(cfg: config::AppConfig, gs: &mut game::state::State) {
     log::debug!("Inserting player into component system ...");
     gs.ecs.insert(character.location);
     gs.ecs.insert(character.entity);
+    gs.ecs.insert(player::creation::Draft::default());
+    gs.ecs.insert(player::activity::AutoActivity::default());
+    gs.ecs.insert(gui::cursor::Cursor::default());
     log::debug!("Entering initial run state ...");
-    gs.ecs.insert(game::state::RunState::PreRun);
+    gs.ecs.insert(game::state::RunState::CreateName);
     log::info!("Completed world setup");
 }
 
@@ -138,20 +170,42 @@ pub fn unload(ecs: &mut World, writer: fs::File) {
         components::Monster,
         components::Name,
         components::BlocksTile,
+        components::Faction,
         components::CombatStats,
+        components::Energy,
+        components::Resistant,
+        components::Vulnerable,
         components::SufferDamage,
+        components::LastHitBy,
+        components::AfflictedBy,
+        components::Experience,
+        components::ExperienceValue,
+        components::Statistics,
+        components::Hunger,
+        components::NaturalRegeneration,
+        components::ProvidesFood,
         components::WantsToMelee,
         components::Item,
         components::Consumable,
+        components::Weight,
         components::Ranged,
         components::InflictsDamage,
         components::AreaOfEffect,
-        components::Confusion,
+        components::InflictsStatus,
+        components::StatusEffects,
         components::ProvidesHealing,
         components::InBackpack,
+        components::InventoryLetters,
+        components::Hotbar,
         components::WantsToPickupItem,
         components::WantsToUseItem,
         components::WantsToDropItem,
+        components::WantsToThrowItem,
+        components::Equippable,
+        components::Equipped,
+        components::EquipmentBonus,
+        components::WantsToEquipItem,
+        components::WantsToRemoveItem,
         components::SerializationHelper
     );
 }
@@ -191,20 +245,42 @@ pub fn load(ecs: &mut World, data: String) {
         components::Monster,
         components::Name,
         components::BlocksTile,
+        components::Faction,
         components::CombatStats,
+        components::Energy,
+        components::Resistant,
+        components::Vulnerable,
         components::SufferDamage,
+        components::LastHitBy,
+        components::AfflictedBy,
+        components::Experience,
+        components::ExperienceValue,
+        components::Statistics,
+        components::Hunger,
+        components::NaturalRegeneration,
+        components::ProvidesFood,
         components::WantsToMelee,
         components::Item,
         components::Consumable,
+        components::Weight,
         components::Ranged,
         components::InflictsDamage,
         components::AreaOfEffect,
-        components::Confusion,
+        components::InflictsStatus,
+        components::StatusEffects,
         components::ProvidesHealing,
         components::InBackpack,
+        components::InventoryLetters,
+        components::Hotbar,
         components::WantsToPickupItem,
         components::WantsToUseItem,
         components::WantsToDropItem,
+        components::WantsToThrowItem,
+        components::Equippable,
+        components::Equipped,
+        components::EquipmentBonus,
+        components::WantsToEquipItem,
+        components::WantsToRemoveItem,
         components::SerializationHelper
     );
 }
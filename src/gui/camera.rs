@@ -0,0 +1,83 @@
+use crate::gui::gui;
+use crate::gui::layout;
+use crate::map;
+use rltk::Point;
+use specs::prelude::*;
+
+/// The part of the map that's on screen. It follows the player, but stops at
+/// the edges of the map so that nothing beyond them is shown; a map smaller
+/// than the screen is drawn from the top left corner, as it always was.
+#[derive(Clone, Copy, Debug)]
+pub struct Camera {
+    pub min_x: i32,
+    pub min_y: i32,
+    pub width: i32,
+    pub height: i32,
+    map_width: i32,
+    map_height: i32,
+}
+
+fn offset(centre: i32, view: i32, size: i32) -> i32 {
+    if size <= view {
+        0
+    } else {
+        i32::max(0, i32::min(size - view, centre - view / 2))
+    }
+}
+
+impl Camera {
+    pub fn new(ecs: &World) -> Self {
+        let gui = ecs.fetch::<gui::GUI>();
+        let game_map = ecs.fetch::<map::Map>();
+        let player_pos = *ecs.fetch::<Point>();
+        let view = layout::Layout::new(&gui).map;
+        let width = view.x2 - view.x1;
+        let height = view.y2 - view.y1;
+        Camera {
+            min_x: offset(player_pos.x, width, game_map.width),
+            min_y: offset(player_pos.y, height, game_map.height),
+            width,
+            height,
+            map_width: game_map.width,
+            map_height: game_map.height,
+        }
+    }
+
+    /// Where a map tile is on screen, if it's in view.
+    pub fn to_screen(&self, pos: Point) -> Option<Point> {
+        let x = pos.x - self.min_x;
+        let y = pos.y - self.min_y;
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(Point::new(x, y))
+    }
+
+    /// Which map tile is at a spot on screen, if any.
+    pub fn to_world(&self, x: i32, y: i32) -> Option<Point> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        let pos = Point::new(x + self.min_x, y + self.min_y);
+        if pos.x >= self.map_width || pos.y >= self.map_height {
+            return None;
+        }
+        Some(pos)
+    }
+
+    /// The tile nearest to a map position that's on screen.
+    pub fn clamp(&self, pos: Point) -> Point {
+        let max_x = i32::min(self.min_x + self.width, self.map_width) - 1;
+        let max_y = i32::min(self.min_y + self.height, self.map_height) - 1;
+        Point::new(
+            i32::max(self.min_x, i32::min(max_x, pos.x)),
+            i32::max(self.min_y, i32::min(max_y, pos.y)),
+        )
+    }
+
+    /// The map tile under the mouse, if any.
+    pub fn mouse(&self, ctx: &rltk::Rltk) -> Option<Point> {
+        let (x, y) = ctx.mouse_pos();
+        self.to_world(x, y)
+    }
+}
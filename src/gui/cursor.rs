@@ -0,0 +1,37 @@
+use crate::gui::camera;
+use crate::player::bindings;
+use rltk::{Point, VirtualKeyCode};
+use specs::prelude::*;
+
+/// A keyboard-driven cursor for looking around and picking targets, for
+/// anyone without a mouse.
+#[derive(Clone, Copy, Debug)]
+pub struct Cursor {
+    pub pos: Point,
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Cursor {
+            pos: Point::new(0, 0),
+        }
+    }
+}
+
+impl Cursor {
+    /// Moves the cursor with the movement keys, keeping it on screen so that
+    /// it can't wander out of sight. Returns false for any other key.
+    pub fn move_by_key(&mut self, key: VirtualKeyCode, camera: &camera::Camera) -> bool {
+        match bindings::direction(key) {
+            None => false,
+            Some((dx, dy)) => {
+                self.pos = camera.clamp(Point::new(self.pos.x + dx, self.pos.y + dy));
+                true
+            }
+        }
+    }
+}
+
+pub fn place(ecs: &World, pos: Point) {
+    ecs.fetch_mut::<Cursor>().pos = pos;
+}
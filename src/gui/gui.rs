@@ -1,8 +1,13 @@
 use crate::components;
 use crate::config;
 use crate::game;
+use crate::gui::layout;
+use crate::gui::sidebar;
 use crate::gui::tooltips;
 use crate::map;
+use crate::player::encumbrance::{self, Encumbrance};
+use crate::player::hotbar;
+use crate::player::hunger::HungerState;
 use rltk::{Console, Rltk, RGB};
 use specs;
 use specs::prelude::*;
@@ -11,85 +16,181 @@ use specs::prelude::*;
 pub struct GUI {
     pub map_area: config::MapArea,
     pub text_area: config::TextArea,
+    pub sidebar: config::Sidebar,
     pub width: i32,
     pub height: i32,
     pub fg_color: (u8, u8, u8),
     pub bg_color: (u8, u8, u8),
     pub cursor_color: (u8, u8, u8),
+    pub theme: config::Theme,
 }
 
 pub fn new(cfg: &config::Gui) -> GUI {
     GUI {
         map_area: cfg.map_area,
         text_area: cfg.text_area,
+        sidebar: cfg.sidebar,
         width: cfg.map_area.width,
         height: cfg.map_area.height + cfg.text_area.height,
         fg_color: cfg.fg_color,
         bg_color: cfg.bg_color,
         cursor_color: cfg.cursor_color,
+        theme: cfg.theme,
     }
 }
 
 pub fn draw(ecs: &World, ctx: &mut Rltk) {
     let gui = ecs.fetch::<GUI>();
+    let layout = layout::Layout::new(&gui);
+    let bg = RGB::named(gui.bg_color);
     ctx.draw_box(
-        0,
-        gui.map_area.height - 1,
-        gui.width - 1,
-        gui.text_area.height,
+        layout.log.x1,
+        layout.log.y1,
+        layout.log.x2 - layout.log.x1,
+        layout.log.y2 - layout.log.y1,
         RGB::named(gui.fg_color),
-        RGB::named(gui.bg_color),
+        bg,
     );
 
+    // The status line: each piece follows on from the last
+    let mut status = layout::Flow::new(layout.log.x1 + 2, layout.status_line());
     let game_map = ecs.fetch::<map::Map>();
-    let depth = format!("Level depth: {}", game_map.depth);
-    // XXX Can we put these numeric values in config?
-    ctx.print_color(
-        2,
-        gui.map_area.height - 1,
-        RGB::named(rltk::YELLOW),
-        RGB::named(rltk::BLACK),
-        &depth,
+    status.print(
+        ctx,
+        RGB::named(gui.theme.status),
+        bg,
+        &format!("Level depth: {}", game_map.depth),
     );
 
     let combat_stats = ecs.read_storage::<components::CombatStats>();
     let players = ecs.read_storage::<components::Player>();
-    for (_player, stats) in (&players, &combat_stats).join() {
-        let health = format!(" HP: {} / {} ", stats.hp, stats.max_hp);
-        // XXX let's calculate the column positions instead of hard-coding them
-        ctx.print_color(
-            20,
-            gui.map_area.height - 1,
-            // XXX add colors to config
-            RGB::named(rltk::YELLOW),
-            RGB::named(rltk::BLACK),
-            &health,
+    let statuses = ecs.read_storage::<components::StatusEffects>();
+    let experience = ecs.read_storage::<components::Experience>();
+    let hunger = ecs.read_storage::<components::Hunger>();
+    let cfg = ecs.fetch::<config::AppConfig>();
+    let entities = ecs.entities();
+    for (player, _player, stats, afflicted, exp, hunger) in (
+        &entities,
+        &players,
+        &combat_stats,
+        statuses.maybe(),
+        experience.maybe(),
+        hunger.maybe(),
+    )
+        .join()
+    {
+        status.print(
+            ctx,
+            RGB::named(gui.theme.status),
+            bg,
+            &format!(" HP: {} / {} ", stats.hp, stats.max_hp),
         );
-        // XXX let's calculate the column positions instead of hard-coding them
-        ctx.draw_bar_horizontal(
-            36,
-            gui.map_area.height - 1,
-            59,
+        status.bar(
+            ctx,
+            layout.health_bar_width(),
             stats.hp,
             stats.max_hp,
-            // XXX add colors to config
-            RGB::named(rltk::GREEN),
-            RGB::named(rltk::BLACK),
+            RGB::named(gui.theme.health),
+            bg,
         );
+        if let Some(exp) = exp {
+            let progress = format!(
+                " Level {}  XP: {} / {} ",
+                exp.level,
+                exp.xp,
+                exp.next_level_xp(&cfg.progression)
+            );
+            status.print(ctx, RGB::named(gui.theme.experience), bg, &progress);
+        }
+        if let Some(hunger) = hunger {
+            let colour = match hunger.state {
+                HungerState::WellFed => Some(gui.theme.good),
+                HungerState::Normal => None,
+                HungerState::Hungry => Some(gui.theme.warning),
+                HungerState::Weak | HungerState::Starving => Some(gui.theme.danger),
+            };
+            if let Some(colour) = colour {
+                status.print(ctx, RGB::named(colour), bg, &format!(" {} ", hunger.state));
+            }
+        }
+        if let Some(load) = encumbrance::load(ecs, player) {
+            let colour = match load.encumbrance {
+                Encumbrance::Unburdened => None,
+                Encumbrance::Burdened => Some(gui.theme.warning),
+                Encumbrance::Overloaded => Some(gui.theme.danger),
+            };
+            if let Some(colour) = colour {
+                status.print(
+                    ctx,
+                    RGB::named(colour),
+                    bg,
+                    &format!(" {} ", load.encumbrance),
+                );
+            }
+        }
+        if let Some(afflicted) = afflicted {
+            status.print(
+                ctx,
+                RGB::named(gui.theme.effects),
+                bg,
+                &afflicted.describe(),
+            );
+        }
     }
 
-    // Render log messages
+    // Render log messages, newest first
     let log = ecs.fetch::<game::log::GameLog>();
-    let mut y = gui.map_area.height;
-    for e in log.entries.iter().rev() {
-        if y < gui.height - 1 {
-            ctx.print(2, y, e);
-        }
-        y += 1;
+    for (y, entry) in layout.message_rows().zip(log.entries().rev()) {
+        print_log_entry(ctx, &gui, &log, entry, layout.log.x1 + 2, y);
     }
 
+    // The hotbar: each slot's key, then what's in it and how many are left
+    let mut bar = layout::Flow::new(layout.log.x1 + 2, layout.hotbar_line());
+    for (i, slot) in hotbar::slots(ecs).iter().enumerate() {
+        let slot = match slot {
+            None => continue,
+            Some(slot) => slot,
+        };
+        let colour = if slot.items.is_empty() {
+            gui.theme.muted
+        } else {
+            gui.fg_color
+        };
+        bar.print(ctx, RGB::named(gui.theme.key), bg, &format!(" F{}", i + 1));
+        bar.print(
+            ctx,
+            RGB::named(colour),
+            bg,
+            &format!("{} x{} ", slot.name, slot.items.len()),
+        );
+    }
+
+    sidebar::draw(ecs, ctx);
+
     // Draw mouse cursor
     let mouse_pos = ctx.mouse_pos();
     ctx.set_bg(mouse_pos.0, mouse_pos.1, RGB::named(gui.cursor_color));
     tooltips::draw(ecs, ctx);
 }
+
+/// Prints a log message a span at a time, each in its own colour.
+pub fn print_log_entry(
+    ctx: &mut Rltk,
+    gui: &GUI,
+    log: &game::log::GameLog,
+    entry: &game::log::LogEntry,
+    x: i32,
+    y: i32,
+) {
+    let mut x = x;
+    for span in entry.spans.iter() {
+        ctx.print_color(
+            x,
+            y,
+            RGB::named(log.span_color(entry, span)),
+            RGB::named(gui.bg_color),
+            &span.text,
+        );
+        x += span.text.chars().count() as i32;
+    }
+}
@@ -0,0 +1,187 @@
+use crate::gui::gui::GUI;
+use crate::rect::Rect;
+use rltk::{Console, Point, Rltk, RGB};
+
+/// Where everything goes on screen, worked out from the window size rather
+/// than fixed coordinates.
+pub struct Layout {
+    pub screen: Rect,
+    /// The part of the screen the camera draws the map into
+    pub map: Rect,
+    /// The box holding the status line and the latest messages
+    pub log: Rect,
+    /// The panel listing what's in view, to the right of the map, if shown
+    pub sidebar: Option<Rect>,
+}
+
+impl Layout {
+    pub fn new(gui: &GUI) -> Self {
+        // The log box's top border shares the last row of the map area
+        let map_height = gui.map_area.height - 1;
+        let sidebar_width = if gui.sidebar.enabled {
+            i32::max(0, i32::min(gui.width / 2, gui.sidebar.width))
+        } else {
+            0
+        };
+        let map_width = gui.width - sidebar_width;
+        Layout {
+            screen: Rect::new(0, 0, gui.width, gui.height),
+            map: Rect::new(0, 0, map_width, map_height),
+            log: Rect::new(0, map_height, gui.width - 1, gui.text_area.height),
+            sidebar: if sidebar_width > 0 {
+                Some(Rect::new(map_width, 0, sidebar_width - 1, map_height))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// The status line runs along the top border of the log box.
+    pub fn status_line(&self) -> i32 {
+        self.log.y1
+    }
+
+    /// The hotbar runs along the bottom border of the log box.
+    pub fn hotbar_line(&self) -> i32 {
+        self.log.y2
+    }
+
+    /// How long the HP bar on the status line is: a quarter of the screen,
+    /// but never too short to read.
+    pub fn health_bar_width(&self) -> i32 {
+        i32::max(10, self.screen.x2 / 4)
+    }
+
+    /// The rows inside the log box, top to bottom.
+    pub fn message_rows(&self) -> std::ops::Range<i32> {
+        self.log.y1 + 1..self.log.y2
+    }
+
+    /// A box of the given size in the middle of the map, kept on screen.
+    pub fn centered(&self, width: i32, height: i32) -> Rect {
+        let (centre_x, centre_y) = self.map.center();
+        let x = i32::max(
+            0,
+            i32::min(self.screen.x2 - width - 1, centre_x - width / 2),
+        );
+        let y = i32::max(
+            0,
+            i32::min(self.screen.y2 - height - 1, centre_y - height / 2),
+        );
+        Rect::new(x, y, width, height)
+    }
+
+    /// The row to start a block of `lines` centred lines on, so that it sits
+    /// in the middle of the screen.
+    pub fn centered_top(&self, lines: i32) -> i32 {
+        i32::max(0, (self.screen.y2 - lines) / 2)
+    }
+
+    /// Which side of a spot a tooltip goes on: left if the spot is on the
+    /// right half of the map.
+    pub fn tooltip_on_left(&self, pos: Point) -> bool {
+        pos.x > self.map.center().0
+    }
+}
+
+/// Draws a framed menu with a title on its top border and a hint under its
+/// rows, sized to fit. Returns where the first row goes.
+pub fn menu_frame(
+    ctx: &mut Rltk,
+    gui: &GUI,
+    width: i32,
+    rows: i32,
+    title: &str,
+    hint: Option<&str>,
+) -> Point {
+    let longest = i32::max(title.len() as i32, hint.map_or(0, |h| h.len() as i32));
+    let width = i32::max(width, longest) + 6;
+    let frame = Layout::new(gui).centered(width, rows + 3);
+    ctx.draw_box(
+        frame.x1,
+        frame.y1,
+        width,
+        rows + 3,
+        RGB::named(gui.fg_color),
+        RGB::named(gui.bg_color),
+    );
+    ctx.print_color(
+        frame.x1 + 3,
+        frame.y1,
+        RGB::named(gui.theme.title),
+        RGB::named(gui.bg_color),
+        title,
+    );
+    if let Some(hint) = hint {
+        ctx.print_color(
+            frame.x1 + 3,
+            frame.y2,
+            RGB::named(gui.theme.hint),
+            RGB::named(gui.bg_color),
+            hint,
+        );
+    }
+    Point::new(frame.x1 + 2, frame.y1 + 2)
+}
+
+/// How much of a menu row the "(a) " in front of its text takes up.
+pub const OPTION_WIDTH: i32 = 4;
+
+/// Prints a menu row that's picked with a letter, as in "(a) Dagger".
+/// Returns where the text after the letter starts.
+pub fn print_option(ctx: &mut Rltk, gui: &GUI, x: i32, y: i32, index: usize, text: &str) -> i32 {
+    let fg = RGB::named(gui.fg_color);
+    let bg = RGB::named(gui.bg_color);
+    ctx.set(x, y, fg, bg, rltk::to_cp437('('));
+    ctx.set(x + 1, y, RGB::named(gui.theme.key), bg, 97 + index as u8);
+    ctx.set(x + 2, y, fg, bg, rltk::to_cp437(')'));
+    ctx.print_color(x + OPTION_WIDTH, y, fg, bg, text);
+    x + OPTION_WIDTH
+}
+
+/// Prints a menu row lined up with the options around it, but dimmed and
+/// with no letter, since it can't be picked.
+pub fn print_unlettered(ctx: &mut Rltk, gui: &GUI, x: i32, y: i32, text: &str) {
+    ctx.print_color(
+        x + OPTION_WIDTH,
+        y,
+        RGB::named(gui.theme.muted),
+        RGB::named(gui.bg_color),
+        text,
+    );
+}
+
+/// Lays pieces of text out left to right along one row, a space apart.
+pub struct Flow {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Flow {
+    pub fn new(x: i32, y: i32) -> Self {
+        Flow { x, y }
+    }
+
+    pub fn print(&mut self, ctx: &mut Rltk, fg: RGB, bg: RGB, text: &str) {
+        ctx.print_color(self.x, self.y, fg, bg, text);
+        self.x += text.chars().count() as i32 + 1;
+    }
+
+    pub fn bar(&mut self, ctx: &mut Rltk, width: i32, value: i32, max: i32, fg: RGB, bg: RGB) {
+        ctx.draw_bar_horizontal(self.x, self.y, width, value, max, fg, bg);
+        self.x += width + 1;
+    }
+}
+
+/// Prints an instruction along the top of the map, for modes that play out
+/// on the map itself.
+pub fn prompt(ctx: &mut Rltk, gui: &GUI, text: &str) {
+    let map = Layout::new(gui).map;
+    ctx.print_color(
+        map.x1 + 5,
+        map.y1,
+        RGB::named(gui.theme.title),
+        RGB::named(gui.bg_color),
+        text,
+    );
+}
@@ -0,0 +1,125 @@
+use crate::components;
+use crate::config;
+use crate::game;
+use crate::gui;
+use crate::gui::layout;
+use crate::gui::menus::item::Result;
+use crate::items::equipment;
+use crate::map;
+use rltk::{Console, Rltk, VirtualKeyCode, RGB};
+use specs;
+use specs::prelude::*;
+
+fn with_gear(base: i32, bonus: i32) -> String {
+    if bonus == 0 {
+        base.to_string()
+    } else {
+        format!("{} ({:+} from gear)", base + bonus, bonus)
+    }
+}
+
+/// Everything there is to know about the player, on one screen.
+pub fn show(gs: &mut game::state::State, ctx: &mut Rltk) -> Result {
+    let cfg = gs.ecs.fetch::<config::AppConfig>();
+    let player_entity = *gs.ecs.fetch::<Entity>();
+    let game_map = gs.ecs.fetch::<map::Map>();
+    let names = gs.ecs.read_storage::<components::Name>();
+    let combat_stats = gs.ecs.read_storage::<components::CombatStats>();
+    let experience = gs.ecs.read_storage::<components::Experience>();
+    let energies = gs.ecs.read_storage::<components::Energy>();
+    let hunger = gs.ecs.read_storage::<components::Hunger>();
+    let statuses = gs.ecs.read_storage::<components::StatusEffects>();
+    let statistics = gs.ecs.read_storage::<components::Statistics>();
+    let equipped = gs.ecs.read_storage::<components::Equipped>();
+    let bonuses = gs.ecs.read_storage::<components::EquipmentBonus>();
+
+    let mut rows: Vec<(&str, String)> = Vec::new();
+    if let Some(name) = names.get(player_entity) {
+        rows.push(("Name", name.name.clone()));
+    }
+    if let Some(exp) = experience.get(player_entity) {
+        rows.push((
+            "Level",
+            format!(
+                "{} (XP {} / {})",
+                exp.level,
+                exp.xp,
+                exp.next_level_xp(&cfg.progression)
+            ),
+        ));
+    }
+    if let Some(stats) = combat_stats.get(player_entity) {
+        let gear = equipment::bonuses(player_entity, &equipped, &bonuses);
+        let damage = gear.damage.unwrap_or(stats.damage);
+        let damage_type = gear.damage_type.unwrap_or(stats.damage_type);
+        rows.push(("Hit Points", format!("{} / {}", stats.hp, stats.max_hp)));
+        rows.push(("Power", with_gear(stats.power, gear.power)));
+        rows.push(("Defense", with_gear(stats.defense, gear.defense)));
+        rows.push(("Absorb", gear.absorb.to_string()));
+        rows.push(("Damage", format!("{} {}", damage, damage_type)));
+    }
+    if let Some(energy) = energies.get(player_entity) {
+        rows.push(("Speed", energy.speed.to_string()));
+    }
+    if let Some(hunger) = hunger.get(player_entity) {
+        let penalty = hunger.state.penalty(&cfg.hunger);
+        if penalty > 0 {
+            rows.push(("Hunger", format!("{} (-{} power)", hunger.state, penalty)));
+        } else {
+            rows.push(("Hunger", hunger.state.to_string()));
+        }
+    }
+    let effects = statuses
+        .get(player_entity)
+        .map(|afflicted| afflicted.describe())
+        .filter(|effects| !effects.is_empty())
+        .unwrap_or_else(|| "none".to_string());
+    rows.push(("Effects", effects));
+    rows.push(("Depth", game_map.depth.to_string()));
+    if let Some(statistics) = statistics.get(player_entity) {
+        rows.push(("Turns taken", statistics.turns_taken.to_string()));
+        rows.push(("Kills", statistics.kills.to_string()));
+    }
+    let count = rows.len();
+
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let bg = RGB::named(gui.bg_color);
+    let label_width = rows
+        .iter()
+        .map(|(label, _)| label.len() + 1)
+        .max()
+        .unwrap_or(0) as i32;
+    let value_width = rows.iter().map(|(_, value)| value.len()).max().unwrap_or(0) as i32;
+    let origin = layout::menu_frame(
+        ctx,
+        &gui,
+        label_width + 1 + value_width,
+        count as i32,
+        "Character",
+        Some("ESCAPE to close"),
+    );
+    let mut y = origin.y;
+
+    for (label, value) in rows.iter() {
+        ctx.print_color(
+            origin.x,
+            y,
+            RGB::named(gui.theme.muted),
+            bg,
+            &format!("{}:", label),
+        );
+        ctx.print_color(
+            origin.x + label_width + 1,
+            y,
+            RGB::named(gui.fg_color),
+            bg,
+            value,
+        );
+        y += 1;
+    }
+
+    match ctx.key {
+        Some(VirtualKeyCode::Escape) | Some(VirtualKeyCode::C) => Result::Cancel,
+        _ => Result::NoResponse,
+    }
+}
@@ -0,0 +1,138 @@
+use crate::config;
+use crate::game;
+use crate::gui;
+use crate::gui::layout;
+use crate::gui::menus::item;
+use crate::player::creation;
+use rltk::{Console, Rltk, VirtualKeyCode, RGB};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Result {
+    NoSelection { selected: usize },
+    Selected { selected: usize },
+    Back,
+}
+
+/// Lets the player type in a name. RETURN accepts it, even if it's empty.
+pub fn name(gs: &mut game::state::State, ctx: &mut Rltk) -> item::Result {
+    let title = gs.ecs.fetch::<config::AppConfig>().game.title.clone();
+    let mut draft = gs.ecs.fetch_mut::<creation::Draft>();
+
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let bg = RGB::named(gui.bg_color);
+    let y = layout::Layout::new(&gui).centered_top(13);
+    ctx.print_color_centered(y, RGB::named(gui.theme.heading), bg, &title);
+    ctx.print_color_centered(y + 7, RGB::named(gui.theme.title), bg, "What is your name?");
+    ctx.print_color_centered(
+        y + 9,
+        RGB::named(gui.fg_color),
+        bg,
+        &format!("{}_", draft.name),
+    );
+    ctx.print_color_centered(y + 12, RGB::named(gui.theme.hint), bg, "RETURN to continue");
+
+    match ctx.key {
+        None => item::Result::NoResponse,
+        Some(VirtualKeyCode::Return) => item::Result::Selected,
+        Some(key) => {
+            draft.edit(key, ctx.shift);
+            item::Result::NoResponse
+        }
+    }
+}
+
+/// Lists the configured classes along with the highlighted one's details.
+pub fn class(gs: &mut game::state::State, ctx: &mut Rltk, selection: usize) -> Result {
+    let cfg = gs.ecs.fetch::<config::AppConfig>();
+    let classes = &cfg.classes;
+    let selection = usize::min(selection, classes.len().saturating_sub(1));
+
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let bg = RGB::named(gui.bg_color);
+    let top = layout::Layout::new(&gui).centered_top(classes.len() as i32 + 15);
+    ctx.print_color_centered(top, RGB::named(gui.theme.heading), bg, &cfg.game.title);
+    ctx.print_color_centered(
+        top + 3,
+        RGB::named(gui.theme.title),
+        bg,
+        "Choose your class",
+    );
+
+    let mut y = top + 6;
+    for (i, class) in classes.iter().enumerate() {
+        let fg = if i == selection {
+            gui.theme.selected
+        } else {
+            gui.fg_color
+        };
+        ctx.print_color_centered(y, RGB::named(fg), bg, &class.name);
+        y += 1;
+    }
+
+    if let Some(class) = classes.get(selection) {
+        y += 1;
+        ctx.print_color_centered(y, RGB::named(gui.theme.muted), bg, &class.description);
+        ctx.print_color_centered(
+            y + 1,
+            RGB::named(gui.theme.muted),
+            bg,
+            &format!(
+                "HP {}  Power {}  Defense {}  Damage {}  Sight {}",
+                class.stats.max_hp,
+                class.stats.power,
+                class.stats.defense,
+                class.stats.damage,
+                class.view_range.tile_count
+            ),
+        );
+        let kit: Vec<&str> = class
+            .kit
+            .iter()
+            .map(|key| match cfg.items.get(key) {
+                None => key.as_str(),
+                Some(item) => item.name.as_str(),
+            })
+            .collect();
+        ctx.print_color_centered(
+            y + 2,
+            RGB::named(gui.theme.muted),
+            bg,
+            &format!("Starts with: {}", kit.join(", ")),
+        );
+    }
+    ctx.print_color_centered(
+        y + 5,
+        RGB::named(gui.theme.hint),
+        bg,
+        "RETURN to begin, ESCAPE to change your name",
+    );
+
+    match ctx.key {
+        None => Result::NoSelection {
+            selected: selection,
+        },
+        Some(key) => match key {
+            VirtualKeyCode::Escape => Result::Back,
+            VirtualKeyCode::Up => Result::NoSelection {
+                selected: if selection == 0 {
+                    classes.len().saturating_sub(1)
+                } else {
+                    selection - 1
+                },
+            },
+            VirtualKeyCode::Down => Result::NoSelection {
+                selected: if selection + 1 >= classes.len() {
+                    0
+                } else {
+                    selection + 1
+                },
+            },
+            VirtualKeyCode::Return => Result::Selected {
+                selected: selection,
+            },
+            _ => Result::NoSelection {
+                selected: selection,
+            },
+        },
+    }
+}
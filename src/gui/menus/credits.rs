@@ -0,0 +1,46 @@
+use crate::game;
+use crate::gui;
+use crate::gui::layout;
+use crate::gui::menus::item::Result;
+use rltk::{Console, Rltk, VirtualKeyCode, RGB};
+use std::fs;
+
+/// The credits, as read from the file named in the config.
+pub struct Credits {
+    pub lines: Vec<String>,
+}
+
+impl Credits {
+    pub fn load(path: &str) -> Self {
+        match fs::read_to_string(path) {
+            Ok(text) => Credits {
+                lines: text.lines().map(|line| line.to_string()).collect(),
+            },
+            Err(err) => {
+                log::error!("Couldn't read credits from {}: {:?}", path, err);
+                Credits {
+                    lines: vec!["The credits couldn't be found.".to_string()],
+                }
+            }
+        }
+    }
+}
+
+pub fn show(gs: &mut game::state::State, ctx: &mut Rltk) -> Result {
+    let credits = gs.ecs.fetch::<Credits>();
+
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let bg = RGB::named(gui.bg_color);
+
+    let mut y = layout::Layout::new(&gui).centered_top(credits.lines.len() as i32 + 2);
+    for line in credits.lines.iter() {
+        ctx.print_color_centered(y, RGB::named(gui.fg_color), bg, line);
+        y += 1;
+    }
+    ctx.print_color_centered(y + 2, RGB::named(gui.theme.title), bg, "ESCAPE to go back");
+
+    match ctx.key {
+        Some(VirtualKeyCode::Escape) | Some(VirtualKeyCode::Return) => Result::Cancel,
+        _ => Result::NoResponse,
+    }
+}
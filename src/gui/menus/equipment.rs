@@ -0,0 +1,98 @@
+use crate::components;
+use crate::game;
+use crate::gui;
+use crate::gui::layout;
+use crate::gui::menus::item::Result;
+use crate::items::equipment;
+use rltk::{Console, Rltk, VirtualKeyCode, RGB};
+use specs;
+use specs::prelude::*;
+
+/// Lists every slot with whatever the player is wearing in it. Picking a worn
+/// item takes it off.
+pub fn show(gs: &mut game::state::State, ctx: &mut Rltk) -> (Result, Option<Entity>) {
+    let player_entity = gs.ecs.fetch::<Entity>();
+    let names = gs.ecs.read_storage::<components::Name>();
+    let equipped = gs.ecs.read_storage::<components::Equipped>();
+    let bonuses = gs.ecs.read_storage::<components::EquipmentBonus>();
+    let entities = gs.ecs.entities();
+
+    let mut rows: Vec<(equipment::EquipmentSlot, Option<Entity>)> = Vec::new();
+    for slot in equipment::EquipmentSlot::all() {
+        let mut worn = (&entities, &equipped)
+            .join()
+            .filter(|(_, e)| e.owner == *player_entity && e.slot == slot)
+            .map(|(item, _)| item);
+        for _ in 0..slot.capacity() {
+            rows.push((slot, worn.next()));
+        }
+    }
+    let count = rows.len();
+
+    let labels: Vec<String> = rows.iter().map(|(slot, _)| format!("{}:", slot)).collect();
+    let values: Vec<&str> = rows
+        .iter()
+        .map(|(_, item)| item.map_or("nothing", |item| &names.get(item).unwrap().name))
+        .collect();
+    let totals = equipment::bonuses(*player_entity, &equipped, &bonuses);
+    let totals_text = format!(
+        "Power {:+}  Defense {:+}  Absorb {}",
+        totals.power, totals.defense, totals.absorb
+    );
+    let label_width = labels.iter().map(|label| label.len()).max().unwrap_or(0) as i32;
+    let value_width = values.iter().map(|value| value.len()).max().unwrap_or(0) as i32;
+
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let bg = RGB::named(gui.bg_color);
+    let origin = layout::menu_frame(
+        ctx,
+        &gui,
+        i32::max(
+            layout::OPTION_WIDTH + label_width + 1 + value_width,
+            totals_text.len() as i32 + 1,
+        ),
+        count as i32 + 2,
+        "Equipment",
+        Some("ESCAPE to cancel"),
+    );
+    let mut y = origin.y;
+
+    for (j, ((_, item), (label, value))) in rows
+        .iter()
+        .zip(labels.iter().zip(values.iter()))
+        .enumerate()
+    {
+        let x = layout::print_option(ctx, &gui, origin.x, y, j, label);
+        let colour = if item.is_some() {
+            gui.fg_color
+        } else {
+            gui.theme.muted
+        };
+        ctx.print_color(x + label_width + 1, y, RGB::named(colour), bg, value);
+        y += 1;
+    }
+
+    ctx.print_color(
+        origin.x + 1,
+        y + 1,
+        RGB::named(gui.theme.heading),
+        bg,
+        &totals_text,
+    );
+
+    match ctx.key {
+        None => (Result::NoResponse, None),
+        Some(key) => match key {
+            VirtualKeyCode::Escape => (Result::Cancel, None),
+            _ => {
+                let selection = rltk::letter_to_option(key);
+                if selection > -1 && selection < count as i32 {
+                    if let Some(item) = rows[selection as usize].1 {
+                        return (Result::Selected, Some(item));
+                    }
+                }
+                (Result::NoResponse, None)
+            }
+        },
+    }
+}
@@ -0,0 +1,45 @@
+use crate::game;
+use crate::gui;
+use crate::gui::layout;
+use crate::gui::menus::item::Result;
+use crate::player::bindings;
+use rltk::{Console, Rltk, VirtualKeyCode, RGB};
+
+/// Lists the keys, straight from the bindings the game actually uses.
+pub fn show(gs: &mut game::state::State, ctx: &mut Rltk) -> Result {
+    let mut rows = bindings::describe();
+    rows.push(("Left click".to_string(), "Travel to a spot on the map"));
+    let count = rows.len();
+
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let bg = RGB::named(gui.bg_color);
+    let keys_width = rows.iter().map(|(keys, _)| keys.len()).max().unwrap_or(0) as i32;
+    let text_width = rows.iter().map(|(_, text)| text.len()).max().unwrap_or(0) as i32;
+    let origin = layout::menu_frame(
+        ctx,
+        &gui,
+        keys_width + text_width + 2,
+        count as i32,
+        "Keys",
+        Some("ESCAPE to close"),
+    );
+    let mut y = origin.y;
+
+    for (keys, description) in rows.iter() {
+        ctx.print_color(origin.x + 1, y, RGB::named(gui.theme.key), bg, keys);
+        ctx.print_color(
+            origin.x + keys_width + 3,
+            y,
+            RGB::named(gui.fg_color),
+            bg,
+            description,
+        );
+        y += 1;
+    }
+
+    match ctx.key {
+        Some(VirtualKeyCode::Escape) => Result::Cancel,
+        Some(VirtualKeyCode::Slash) if ctx.shift => Result::Cancel,
+        _ => Result::NoResponse,
+    }
+}
@@ -0,0 +1,119 @@
+use crate::game;
+use crate::game::log::Category;
+use crate::gui;
+use crate::gui::layout;
+use rltk::{Console, Rltk, VirtualKeyCode, RGB};
+
+pub enum Result {
+    Close,
+    Browse {
+        scroll: usize,
+        filter: Option<Category>,
+    },
+}
+
+/// Steps through showing everything, then each category in turn.
+fn next_filter(filter: Option<Category>) -> Option<Category> {
+    let all = Category::all();
+    match filter {
+        None => Some(all[0]),
+        Some(category) => {
+            let i = all.iter().position(|c| *c == category).unwrap_or(0);
+            all.get(i + 1).copied()
+        }
+    }
+}
+
+/// The whole message log, newest at the bottom. `scroll` is how many lines
+/// back from the newest message the view has been moved.
+pub fn show(
+    gs: &mut game::state::State,
+    ctx: &mut Rltk,
+    scroll: usize,
+    filter: Option<Category>,
+) -> Result {
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let log = gs.ecs.fetch::<game::log::GameLog>();
+    let entries: Vec<_> = log
+        .entries()
+        .filter(|entry| filter.map_or(true, |category| entry.category == category))
+        .collect();
+
+    let screen = layout::Layout::new(&gui).screen;
+    let bg = RGB::named(gui.bg_color);
+    let rows = (screen.y2 - screen.y1 - 4) as usize;
+    let max_scroll = entries.len().saturating_sub(rows);
+    let scroll = usize::min(scroll, max_scroll);
+
+    ctx.draw_box(
+        screen.x1,
+        screen.y1,
+        screen.x2 - screen.x1 - 1,
+        screen.y2 - screen.y1 - 1,
+        RGB::named(gui.fg_color),
+        bg,
+    );
+    let shown = filter.map_or("All".to_string(), |category| category.to_string());
+    ctx.print_color(
+        screen.x1 + 3,
+        screen.y1,
+        RGB::named(gui.theme.title),
+        bg,
+        &format!("Message Log: {}", shown),
+    );
+    ctx.print_color(
+        screen.x1 + 3,
+        screen.y2 - 1,
+        RGB::named(gui.theme.title),
+        bg,
+        "TAB to filter, UP/DOWN/PAGE UP/PAGE DOWN to scroll, ESCAPE to close",
+    );
+
+    let end = entries.len() - scroll;
+    let start = end.saturating_sub(rows);
+    let mut y = screen.y1 + 2;
+    for entry in entries[start..end].iter() {
+        ctx.print_color(
+            screen.x1 + 2,
+            y,
+            RGB::named(gui.theme.muted),
+            bg,
+            &format!("{:>6}", entry.turn),
+        );
+        gui::print_log_entry(ctx, &gui, &log, entry, screen.x1 + 10, y);
+        y += 1;
+    }
+
+    match ctx.key {
+        None => Result::Browse { scroll, filter },
+        Some(key) => match key {
+            VirtualKeyCode::Escape => Result::Close,
+            VirtualKeyCode::Tab => Result::Browse {
+                scroll: 0,
+                filter: next_filter(filter),
+            },
+            VirtualKeyCode::Up | VirtualKeyCode::W => Result::Browse {
+                scroll: usize::min(scroll + 1, max_scroll),
+                filter,
+            },
+            VirtualKeyCode::Down | VirtualKeyCode::S => Result::Browse {
+                scroll: scroll.saturating_sub(1),
+                filter,
+            },
+            VirtualKeyCode::PageUp => Result::Browse {
+                scroll: usize::min(scroll + rows, max_scroll),
+                filter,
+            },
+            VirtualKeyCode::PageDown => Result::Browse {
+                scroll: scroll.saturating_sub(rows),
+                filter,
+            },
+            VirtualKeyCode::Home => Result::Browse {
+                scroll: max_scroll,
+                filter,
+            },
+            VirtualKeyCode::End => Result::Browse { scroll: 0, filter },
+            _ => Result::Browse { scroll, filter },
+        },
+    }
+}
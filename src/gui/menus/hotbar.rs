@@ -0,0 +1,37 @@
+use crate::components;
+use crate::game;
+use crate::gui;
+use crate::gui::layout;
+use crate::player::bindings::{self, Command};
+use rltk::{Rltk, VirtualKeyCode};
+use specs::prelude::*;
+
+pub enum Result {
+    Cancel,
+    NoResponse,
+    Selected(usize),
+}
+
+/// Asks which hotbar slot an item goes in, answered with the slot's key.
+pub fn slot(gs: &mut game::state::State, ctx: &mut Rltk, item: Entity) -> Result {
+    let name = gs
+        .ecs
+        .read_storage::<components::Name>()
+        .get(item)
+        .map_or("item".to_string(), |name| name.name.clone());
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    layout::prompt(
+        ctx,
+        &gui,
+        &format!("Put the {} on which key? (F1-F9, ESCAPE to cancel)", name),
+    );
+
+    match ctx.key {
+        Some(VirtualKeyCode::Escape) => Result::Cancel,
+        Some(key) => match bindings::command(key, false) {
+            Some(Command::Hotbar(slot)) => Result::Selected(slot),
+            _ => Result::NoResponse,
+        },
+        None => Result::NoResponse,
+    }
+}
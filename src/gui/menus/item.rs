@@ -1,5 +1,9 @@
 use crate::components;
 use crate::game;
+use crate::gui;
+use crate::gui::layout;
+use crate::items;
+use crate::player::encumbrance::{self, Encumbrance};
 use rltk::{Console, Rltk, VirtualKeyCode, RGB};
 use specs;
 use specs::prelude::*;
@@ -11,157 +15,138 @@ pub enum Result {
     Selected,
 }
 
-pub fn show_inventory(gs: &mut game::state::State, ctx: &mut Rltk) -> (Result, Option<Entity>) {
-    let player_entity = gs.ecs.fetch::<Entity>();
-    let names = gs.ecs.read_storage::<components::Name>();
-    let backpack = gs.ecs.read_storage::<components::InBackpack>();
-    let entities = gs.ecs.entities();
-
-    let inventory = (&backpack, &names)
-        .join()
-        .filter(|item| item.0.owner == *player_entity);
-    let count = inventory.count();
-
-    let mut y = (25 - (count / 2)) as i32;
-    ctx.draw_box(
-        15,
-        y - 2,
-        31,
-        (count + 3) as i32,
-        RGB::named(rltk::WHITE),
-        RGB::named(rltk::BLACK),
-    );
-    ctx.print_color(
-        18,
-        y - 2,
-        RGB::named(rltk::YELLOW),
-        RGB::named(rltk::BLACK),
-        "Inventory",
-    );
-    ctx.print_color(
-        18,
-        y + count as i32 + 1,
-        RGB::named(rltk::DARK_GREEN),
-        RGB::named(rltk::BLACK),
-        "ESCAPE to cancel",
-    );
+/// Identical items in the backpack, listed on one line.
+pub struct Stack {
+    pub name: String,
+    pub letter: Option<char>,
+    pub category: items::Category,
+    pub items: Vec<Entity>,
+}
 
-    let mut equippable: Vec<Entity> = Vec::new();
-    let mut j = 0;
-    for (entity, _pack, name) in (&entities, &backpack, &names)
-        .join()
-        .filter(|item| item.1.owner == *player_entity)
+/// The player's backpack grouped into stacks of identical items, sorted by
+/// category and then by name. Each kind of item is listed under the letter it
+/// was given when it went into the pack.
+pub fn stacks(ecs: &World) -> Vec<Stack> {
+    let player_entity = *ecs.fetch::<Entity>();
+    let mut stacks: Vec<Stack> = Vec::new();
     {
-        ctx.set(
-            17,
-            y,
-            RGB::named(rltk::WHITE),
-            RGB::named(rltk::BLACK),
-            rltk::to_cp437('('),
-        );
-        ctx.set(
-            18,
-            y,
-            RGB::named(rltk::YELLOW),
-            RGB::named(rltk::BLACK),
-            97 + j as u8,
-        );
-        ctx.set(
-            19,
-            y,
-            RGB::named(rltk::WHITE),
-            RGB::named(rltk::BLACK),
-            rltk::to_cp437(')'),
-        );
-
-        ctx.print(21, y, &name.name.to_string());
-        equippable.push(entity);
-        y += 1;
-        j += 1;
+        let names = ecs.read_storage::<components::Name>();
+        let backpack = ecs.read_storage::<components::InBackpack>();
+        let entities = ecs.entities();
+        for (entity, _pack, name) in (&entities, &backpack, &names)
+            .join()
+            .filter(|item| item.1.owner == player_entity)
+        {
+            match stacks.iter_mut().find(|stack| stack.name == name.name) {
+                Some(stack) => stack.items.push(entity),
+                None => stacks.push(Stack {
+                    name: name.name.clone(),
+                    letter: None,
+                    category: items::category(ecs, entity),
+                    items: vec![entity],
+                }),
+            }
+        }
     }
 
-    match ctx.key {
-        None => (Result::NoResponse, None),
-        Some(key) => match key {
-            VirtualKeyCode::Escape => (Result::Cancel, None),
-            _ => {
-                let selection = rltk::letter_to_option(key);
-                if selection > -1 && selection < count as i32 {
-                    return (Result::Selected, Some(equippable[selection as usize]));
-                }
-                (Result::NoResponse, None)
-            }
-        },
+    if let Some(letters) = ecs
+        .read_storage::<components::InventoryLetters>()
+        .get(player_entity)
+    {
+        for stack in stacks.iter_mut() {
+            stack.letter = letters.letter(&stack.name);
+        }
     }
+    stacks.sort_by(|a, b| (a.category, &a.name).cmp(&(b.category, &b.name)));
+    stacks
 }
 
-pub fn drop(gs: &mut game::state::State, ctx: &mut Rltk) -> (Result, Option<Entity>) {
-    let player_entity = gs.ecs.fetch::<Entity>();
-    let names = gs.ecs.read_storage::<components::Name>();
-    let backpack = gs.ecs.read_storage::<components::InBackpack>();
-    let entities = gs.ecs.entities();
+/// Lists the backpack a stack to a line under category headings, and hands
+/// back an item from whichever stack's letter is pressed.
+pub fn inventory_menu(
+    gs: &mut game::state::State,
+    ctx: &mut Rltk,
+    title: &str,
+) -> (Result, Option<Entity>) {
+    let stacks = stacks(&gs.ecs);
+    let mut categories: Vec<items::Category> = stacks.iter().map(|stack| stack.category).collect();
+    categories.dedup();
 
-    let inventory = (&backpack, &names)
-        .join()
-        .filter(|item| item.0.owner == *player_entity);
-    let count = inventory.count();
+    let player_entity = *gs.ecs.fetch::<Entity>();
+    let load = encumbrance::load(&gs.ecs, player_entity);
 
-    let mut y = (25 - (count / 2)) as i32;
-    ctx.draw_box(
-        15,
-        y - 2,
-        31,
-        (count + 3) as i32,
-        RGB::named(rltk::WHITE),
-        RGB::named(rltk::BLACK),
-    );
-    ctx.print_color(
-        18,
-        y - 2,
-        RGB::named(rltk::YELLOW),
-        RGB::named(rltk::BLACK),
-        "Drop Which Item?",
-    );
-    ctx.print_color(
-        18,
-        y + count as i32 + 1,
-        RGB::named(rltk::DARK_GREEN),
-        RGB::named(rltk::BLACK),
-        "ESCAPE to cancel",
-    );
+    let texts: Vec<String> = stacks
+        .iter()
+        .map(|stack| {
+            if stack.items.len() > 1 {
+                format!("{} (x{})", stack.name, stack.items.len())
+            } else {
+                stack.name.clone()
+            }
+        })
+        .collect();
+    let load_text = load.map(|load| {
+        format!(
+            "Weight: {} / {} ({})",
+            load.carried, load.capacity, load.encumbrance
+        )
+    });
+    let width = texts
+        .iter()
+        .map(|text| text.len() as i32 + layout::OPTION_WIDTH + 1)
+        .chain(categories.iter().map(|c| c.to_string().len() as i32))
+        .chain(load_text.iter().map(|text| text.len() as i32))
+        .max()
+        .unwrap_or(0);
 
-    let mut equippable: Vec<Entity> = Vec::new();
-    let mut j = 0;
-    for (entity, _pack, name) in (&entities, &backpack, &names)
-        .join()
-        .filter(|item| item.1.owner == *player_entity)
-    {
-        ctx.set(
-            17,
-            y,
-            RGB::named(rltk::WHITE),
-            RGB::named(rltk::BLACK),
-            rltk::to_cp437('('),
-        );
-        ctx.set(
-            18,
-            y,
-            RGB::named(rltk::YELLOW),
-            RGB::named(rltk::BLACK),
-            97 + j as u8,
-        );
-        ctx.set(
-            19,
-            y,
-            RGB::named(rltk::WHITE),
-            RGB::named(rltk::BLACK),
-            rltk::to_cp437(')'),
-        );
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let origin = layout::menu_frame(
+        ctx,
+        &gui,
+        width,
+        (stacks.len() + categories.len()) as i32 + 2,
+        title,
+        Some("ESCAPE to cancel"),
+    );
+    let mut y = origin.y;
 
-        ctx.print(21, y, &name.name.to_string());
-        equippable.push(entity);
+    let mut heading = None;
+    for (stack, text) in stacks.iter().zip(texts.iter()) {
+        if heading != Some(stack.category) {
+            heading = Some(stack.category);
+            ctx.print_color(
+                origin.x,
+                y,
+                RGB::named(gui.theme.muted),
+                RGB::named(gui.bg_color),
+                &stack.category.to_string(),
+            );
+            y += 1;
+        }
+        match stack.letter {
+            Some(letter) => {
+                let index = letter as usize - 'a' as usize;
+                layout::print_option(ctx, &gui, origin.x + 1, y, index, text);
+            }
+            // Out of letters, so it's listed but can't be picked
+            None => layout::print_unlettered(ctx, &gui, origin.x + 1, y, text),
+        }
         y += 1;
-        j += 1;
+    }
+
+    if let (Some(load), Some(load_text)) = (load, load_text) {
+        let colour = match load.encumbrance {
+            Encumbrance::Unburdened => gui.theme.muted,
+            Encumbrance::Burdened => gui.theme.warning,
+            Encumbrance::Overloaded => gui.theme.danger,
+        };
+        ctx.print_color(
+            origin.x,
+            y + 1,
+            RGB::named(colour),
+            RGB::named(gui.bg_color),
+            &load_text,
+        );
     }
 
     match ctx.key {
@@ -170,11 +155,31 @@ pub fn drop(gs: &mut game::state::State, ctx: &mut Rltk) -> (Result, Option<Enti
             VirtualKeyCode::Escape => (Result::Cancel, None),
             _ => {
                 let selection = rltk::letter_to_option(key);
-                if selection > -1 && selection < count as i32 {
-                    return (Result::Selected, Some(equippable[selection as usize]));
+                if selection < 0 {
+                    return (Result::NoResponse, None);
+                }
+                let letter = (b'a' + selection as u8) as char;
+                match stacks.iter().find(|stack| stack.letter == Some(letter)) {
+                    Some(stack) => (Result::Selected, Some(stack.items[0])),
+                    None => (Result::NoResponse, None),
                 }
-                (Result::NoResponse, None)
             }
         },
     }
 }
+
+pub fn show_inventory(gs: &mut game::state::State, ctx: &mut Rltk) -> (Result, Option<Entity>) {
+    inventory_menu(gs, ctx, "Inventory")
+}
+
+pub fn drop(gs: &mut game::state::State, ctx: &mut Rltk) -> (Result, Option<Entity>) {
+    inventory_menu(gs, ctx, "Drop Which Item?")
+}
+
+pub fn throw(gs: &mut game::state::State, ctx: &mut Rltk) -> (Result, Option<Entity>) {
+    inventory_menu(gs, ctx, "Throw Which Item?")
+}
+
+pub fn hotbar(gs: &mut game::state::State, ctx: &mut Rltk) -> (Result, Option<Entity>) {
+    inventory_menu(gs, ctx, "Put Which Item In The Hotbar?")
+}
@@ -0,0 +1,52 @@
+use crate::components;
+use crate::config;
+use crate::game;
+use crate::gui;
+use crate::gui::layout;
+use crate::gui::menus::item::Result;
+use crate::player::progression::Stat;
+use rltk::Rltk;
+use specs;
+use specs::prelude::*;
+
+/// Asks the player which stat to raise. There's no cancelling: the choice
+/// has to be made before play carries on.
+pub fn show(gs: &mut game::state::State, ctx: &mut Rltk) -> (Result, Option<Stat>) {
+    let cfg = gs.ecs.fetch::<config::AppConfig>();
+    let player_entity = gs.ecs.fetch::<Entity>();
+    let combat_stats = gs.ecs.read_storage::<components::CombatStats>();
+    let experience = gs.ecs.read_storage::<components::Experience>();
+
+    let stats = combat_stats.get(*player_entity).unwrap();
+    let level = experience.get(*player_entity).map_or(1, |exp| exp.level);
+    let step = cfg.progression.stat_per_level;
+    let choices = [(Stat::Power, stats.power), (Stat::Defense, stats.defense)];
+
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let origin = layout::menu_frame(
+        ctx,
+        &gui,
+        35,
+        choices.len() as i32,
+        &format!("Level {}! Choose a stat to raise", level),
+        None,
+    );
+    let mut y = origin.y;
+
+    for (j, (stat, value)) in choices.iter().enumerate() {
+        let text = format!("{}: {} -> {}", stat, value, value + step);
+        layout::print_option(ctx, &gui, origin.x, y, j, &text);
+        y += 1;
+    }
+
+    match ctx.key {
+        None => (Result::NoResponse, None),
+        Some(key) => {
+            let selection = rltk::letter_to_option(key);
+            if selection > -1 && selection < choices.len() as i32 {
+                return (Result::Selected, Some(choices[selection as usize].0));
+            }
+            (Result::NoResponse, None)
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::game;
+use crate::gui;
+use crate::gui::camera;
+use crate::gui::cursor;
+use crate::gui::layout;
+use crate::gui::menus::item::Result;
+use crate::gui::tooltips;
+use crate::map;
+use rltk::{Console, Rltk, VirtualKeyCode, RGB};
+
+/// Moves a cursor around the map with the movement keys, describing whatever
+/// is under it.
+pub fn show(gs: &mut game::state::State, ctx: &mut Rltk) -> Result {
+    let camera = camera::Camera::new(&gs.ecs);
+    let mut cursor = gs.ecs.fetch_mut::<cursor::Cursor>();
+    if let Some(key) = ctx.key {
+        match key {
+            VirtualKeyCode::Escape | VirtualKeyCode::V => return Result::Cancel,
+            _ => {
+                cursor.move_by_key(key, &camera);
+            }
+        }
+    }
+
+    let pos = cursor.pos;
+    let (terrain, lines) = {
+        let game_map = gs.ecs.fetch::<map::Map>();
+        let idx = game_map.xy_idx(pos.x, pos.y);
+        let terrain = if !game_map.revealed_tiles[idx] {
+            "Unexplored".to_string()
+        } else {
+            let name = match game_map.tiles[idx] {
+                map::TileType::Wall => "Wall",
+                map::TileType::Floor => "Floor",
+                map::TileType::Exit(map::ExitDirection::Down) => "Stairs down",
+                map::TileType::Exit(_) => "Exit",
+            };
+            if game_map.visible_tiles[idx] {
+                name.to_string()
+            } else {
+                format!("{} (remembered)", name)
+            }
+        };
+        drop(game_map);
+        (terrain, tooltips::describe(&gs.ecs, pos.x, pos.y))
+    };
+
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    layout::prompt(ctx, &gui, &format!("Look: {} (ESCAPE to stop)", terrain));
+    if let Some(screen) = camera.to_screen(pos) {
+        ctx.set_bg(screen.x, screen.y, RGB::named(gui.theme.target_cursor));
+        tooltips::draw_at(&gs.ecs, ctx, screen, &lines);
+    }
+
+    Result::NoResponse
+}
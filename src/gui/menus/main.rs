@@ -1,11 +1,14 @@
 use crate::config;
 use crate::game;
 use crate::game::persistence;
+use crate::gui;
+use crate::gui::layout;
 use rltk::{Console, Rltk, VirtualKeyCode, RGB};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Selection {
     ContinuePlaying,
+    Character,
     NewGame,
     SaveGame,
     LoadGame,
@@ -24,103 +27,37 @@ pub fn draw(gs: &mut game::state::State, ctx: &mut Rltk) -> Result {
     let cfg = gs.ecs.fetch::<config::AppConfig>().game.clone();
     let runstate = gs.ecs.fetch::<game::state::RunState>();
 
-    ctx.print_color_centered(
-        15,
-        RGB::named(rltk::DARK_GREEN),
-        RGB::named(rltk::BLACK),
-        &cfg.title,
-    );
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    let bg = RGB::named(gui.bg_color);
+    let mut entries = vec![
+        (Selection::ContinuePlaying, "Return to Game"),
+        (Selection::Character, "Character Sheet"),
+        (Selection::NewGame, "Begin New Game"),
+        (Selection::SaveGame, "Save Game"),
+    ];
+    if save_exists {
+        entries.push((Selection::LoadGame, "Load Game"));
+    }
+    entries.push((Selection::Credits, "Credits"));
+    entries.push((Selection::Quit, "Quit"));
+
+    // The title, a gap, then one row per entry
+    let mut y = layout::Layout::new(&gui).centered_top(entries.len() as i32 + 9);
+    ctx.print_color_centered(y, RGB::named(gui.theme.heading), bg, &cfg.title);
+    y += 9;
 
     if let game::state::RunState::MainMenu {
         menu_selection: selection,
     } = *runstate
     {
-        if selection == Selection::ContinuePlaying {
-            ctx.print_color_centered(
-                24,
-                RGB::named(rltk::GREEN),
-                RGB::named(rltk::BLACK),
-                "Return to Game",
-            );
-        } else {
-            ctx.print_color_centered(
-                24,
-                RGB::named(rltk::WHITE),
-                RGB::named(rltk::BLACK),
-                "Return to Game",
-            );
-        }
-
-        if selection == Selection::NewGame {
-            ctx.print_color_centered(
-                25,
-                RGB::named(rltk::GREEN),
-                RGB::named(rltk::BLACK),
-                "Begin New Game",
-            );
-        } else {
-            ctx.print_color_centered(
-                25,
-                RGB::named(rltk::WHITE),
-                RGB::named(rltk::BLACK),
-                "Begin New Game",
-            );
-        }
-
-        if selection == Selection::SaveGame {
-            ctx.print_color_centered(
-                26,
-                RGB::named(rltk::GREEN),
-                RGB::named(rltk::BLACK),
-                "Save Game",
-            );
-        } else {
-            ctx.print_color_centered(
-                26,
-                RGB::named(rltk::WHITE),
-                RGB::named(rltk::BLACK),
-                "Save Game",
-            );
-        }
-
-        if save_exists {
-            if selection == Selection::LoadGame {
-                ctx.print_color_centered(
-                    27,
-                    RGB::named(rltk::GREEN),
-                    RGB::named(rltk::BLACK),
-                    "Load Game",
-                );
+        for (entry, text) in entries.iter() {
+            let fg = if *entry == selection {
+                gui.theme.selected
             } else {
-                ctx.print_color_centered(
-                    27,
-                    RGB::named(rltk::WHITE),
-                    RGB::named(rltk::BLACK),
-                    "Load Game",
-                );
-            }
-        }
-
-        if selection == Selection::Credits {
-            ctx.print_color_centered(
-                28,
-                RGB::named(rltk::GREEN),
-                RGB::named(rltk::BLACK),
-                "Credits",
-            );
-        } else {
-            ctx.print_color_centered(
-                28,
-                RGB::named(rltk::WHITE),
-                RGB::named(rltk::BLACK),
-                "Credits",
-            );
-        }
-
-        if selection == Selection::Quit {
-            ctx.print_color_centered(29, RGB::named(rltk::GREEN), RGB::named(rltk::BLACK), "Quit");
-        } else {
-            ctx.print_color_centered(29, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), "Quit");
+                gui.fg_color
+            };
+            ctx.print_color_centered(y, RGB::named(fg), bg, text);
+            y += 1;
         }
 
         match ctx.key {
@@ -141,7 +78,8 @@ pub fn draw(gs: &mut game::state::State, ctx: &mut Rltk) -> Result {
                         let mut newselection;
                         match selection {
                             Selection::ContinuePlaying => newselection = Selection::Quit,
-                            Selection::NewGame => newselection = Selection::ContinuePlaying,
+                            Selection::Character => newselection = Selection::ContinuePlaying,
+                            Selection::NewGame => newselection = Selection::Character,
                             Selection::SaveGame => newselection = Selection::NewGame,
                             Selection::LoadGame => newselection = Selection::SaveGame,
                             Selection::Credits => newselection = Selection::LoadGame,
@@ -157,7 +95,8 @@ pub fn draw(gs: &mut game::state::State, ctx: &mut Rltk) -> Result {
                     VirtualKeyCode::Down => {
                         let mut newselection;
                         match selection {
-                            Selection::ContinuePlaying => newselection = Selection::NewGame,
+                            Selection::ContinuePlaying => newselection = Selection::Character,
+                            Selection::Character => newselection = Selection::NewGame,
                             Selection::NewGame => newselection = Selection::SaveGame,
                             Selection::SaveGame => newselection = Selection::LoadGame,
                             Selection::LoadGame => newselection = Selection::Credits,
@@ -1,3 +1,12 @@
+pub mod character;
+pub mod creation;
+pub mod credits;
+pub mod equipment;
+pub mod help;
+pub mod history;
+pub mod hotbar;
 pub mod item;
+pub mod level_up;
+pub mod look;
 pub mod main;
 pub mod target;
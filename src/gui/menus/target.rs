@@ -1,25 +1,71 @@
+use crate::combat::projectile;
 use crate::components;
+use crate::faction;
 use crate::game;
+use crate::gui;
+use crate::gui::camera;
+use crate::gui::cursor;
+use crate::gui::layout;
 use crate::gui::menus;
-use rltk::{Console, Point, Rltk, RGB};
+use crate::map;
+use rltk::{Console, Point, Rltk, VirtualKeyCode, RGB};
 use specs;
 use specs::prelude::*;
 
+/// Visible hostiles within range of the player, nearest first.
+fn hostiles_in_range(ecs: &World, range: i32) -> Vec<Point> {
+    let player_entity = *ecs.fetch::<Entity>();
+    let player_pos = *ecs.fetch::<Point>();
+    let positions = ecs.read_storage::<components::Position>();
+    faction::visible_hostiles(ecs, player_entity)
+        .into_iter()
+        .filter_map(|hostile| positions.get(hostile))
+        .map(|pos| Point::new(pos.x, pos.y))
+        .filter(|pos| rltk::DistanceAlg::Pythagoras.distance2d(player_pos, *pos) <= range as f32)
+        .collect()
+}
+
+/// Puts the targeting cursor on the nearest hostile in range, or on the
+/// player if there isn't one.
+pub fn begin(ecs: &World, range: i32) {
+    let player_pos = *ecs.fetch::<Point>();
+    let start = hostiles_in_range(ecs, range)
+        .first()
+        .copied()
+        .unwrap_or(player_pos);
+    cursor::place(ecs, start);
+}
+
+/// What the targeting screen shows of the shot on its way to the target.
+#[derive(PartialEq, Copy, Clone)]
+pub enum Preview {
+    /// Nothing; area effects go off wherever they're aimed
+    None,
+    /// The bolt's path and the tile it hits
+    Bolt,
+    /// The throw's path and the tile the item comes to rest on
+    Throw,
+}
+
+/// Picks a target tile, either with the mouse or with the keyboard cursor:
+/// the movement keys move it, TAB jumps between hostiles in range and RETURN
+/// fires.
 pub fn ranged(
     gs: &mut game::state::State,
     ctx: &mut Rltk,
     range: i32,
+    preview: Preview,
 ) -> (menus::item::Result, Option<Point>) {
     let player_entity = gs.ecs.fetch::<Entity>();
     let player_pos = gs.ecs.fetch::<Point>();
     let viewsheds = gs.ecs.read_storage::<components::Viewshed>();
+    let camera = camera::Camera::new(&gs.ecs);
 
-    ctx.print_color(
-        5,
-        0,
-        RGB::named(rltk::YELLOW),
-        RGB::named(rltk::BLACK),
-        "Select Target:",
+    let gui = gs.ecs.fetch::<gui::GUI>();
+    layout::prompt(
+        ctx,
+        &gui,
+        "Select Target: (TAB to cycle, RETURN to fire, ESCAPE to cancel)",
     );
 
     // Highlight available target cells
@@ -30,34 +76,91 @@ pub fn ranged(
         for idx in visible.visible_tiles.iter() {
             let distance = rltk::DistanceAlg::Pythagoras.distance2d(*player_pos, *idx);
             if distance <= range as f32 {
-                ctx.set_bg(idx.x, idx.y, RGB::named(rltk::BLUE));
-                available_cells.push(idx);
+                if let Some(screen) = camera.to_screen(*idx) {
+                    ctx.set_bg(screen.x, screen.y, RGB::named(gui.theme.target_range));
+                }
+                available_cells.push(*idx);
             }
         }
     } else {
         return (menus::item::Result::Cancel, None);
     }
+    let is_available = |x: i32, y: i32| available_cells.iter().any(|p| p.x == x && p.y == y);
+
+    // Keyboard cursor
+    let mut cursor = gs.ecs.fetch_mut::<cursor::Cursor>();
+    if let Some(key) = ctx.key {
+        match key {
+            VirtualKeyCode::Escape => return (menus::item::Result::Cancel, None),
+            VirtualKeyCode::Return => {
+                if is_available(cursor.pos.x, cursor.pos.y) {
+                    return (menus::item::Result::Selected, Some(cursor.pos));
+                }
+            }
+            VirtualKeyCode::Tab => {
+                let targets = hostiles_in_range(&gs.ecs, range);
+                if !targets.is_empty() {
+                    let next = match targets.iter().position(|p| *p == cursor.pos) {
+                        None => 0,
+                        Some(i) => (i + 1) % targets.len(),
+                    };
+                    cursor.pos = targets[next];
+                }
+            }
+            _ => {
+                cursor.move_by_key(key, &camera);
+            }
+        }
+    }
+
+    let mut impact = None;
+    if preview != Preview::None {
+        let aim = match camera.mouse(ctx) {
+            Some(pos) if is_available(pos.x, pos.y) => pos,
+            _ => cursor.pos,
+        };
+        let game_map = gs.ecs.fetch::<map::Map>();
+        let path = projectile::trace(&game_map, *player_pos, aim);
+        for pos in path.iter() {
+            if let Some(screen) = camera.to_screen(*pos) {
+                ctx.set_bg(screen.x, screen.y, RGB::named(gui.theme.target_path));
+            }
+        }
+        impact = match preview {
+            Preview::Throw => Some(projectile::landing(&game_map, *player_pos, aim)),
+            _ => Some(projectile::impact(&game_map, *player_pos, aim)),
+        };
+    }
+
+    if let Some(screen) = camera.to_screen(cursor.pos) {
+        if is_available(cursor.pos.x, cursor.pos.y) {
+            ctx.set_bg(screen.x, screen.y, RGB::named(gui.theme.target_cursor));
+        } else {
+            ctx.set_bg(screen.x, screen.y, RGB::named(gui.theme.target_invalid));
+        }
+    }
 
     // Draw mouse cursor
-    let mouse_pos = ctx.mouse_pos();
-    let mut valid_target = false;
-    for idx in available_cells.iter() {
-        if idx.x == mouse_pos.0 && idx.y == mouse_pos.1 {
-            valid_target = true;
+    let mouse_pos = camera.mouse(ctx);
+    if let Some(pos) = mouse_pos {
+        if is_available(pos.x, pos.y) {
+            let (x, y) = ctx.mouse_pos();
+            ctx.set_bg(x, y, RGB::named(gui.theme.target_cursor));
         }
     }
-    if valid_target {
-        ctx.set_bg(mouse_pos.0, mouse_pos.1, RGB::named(rltk::CYAN));
-        if ctx.left_click {
-            return (
-                menus::item::Result::Selected,
-                Some(Point::new(mouse_pos.0, mouse_pos.1)),
-            );
+    if let Some(screen) = impact.and_then(|pos| camera.to_screen(pos)) {
+        ctx.set_bg(screen.x, screen.y, RGB::named(gui.theme.target_impact));
+    }
+    match mouse_pos {
+        Some(pos) if is_available(pos.x, pos.y) => {
+            if ctx.left_click {
+                return (menus::item::Result::Selected, Some(pos));
+            }
         }
-    } else {
-        ctx.set_bg(mouse_pos.0, mouse_pos.1, RGB::named(rltk::RED));
-        if ctx.left_click {
-            return (menus::item::Result::Cancel, None);
+        _ => {
+            if ctx.left_click {
+                return (menus::item::Result::Cancel, None);
+            }
         }
     }
 
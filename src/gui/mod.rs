@@ -1,5 +1,9 @@
+pub mod camera;
+pub mod cursor;
 pub mod gui;
+pub mod layout;
 pub mod menus;
+pub mod sidebar;
 pub mod tooltips;
 
 pub use self::gui::*;
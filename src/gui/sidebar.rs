@@ -0,0 +1,153 @@
+use crate::components;
+use crate::faction;
+use crate::gui::camera;
+use crate::gui::gui::GUI;
+use crate::gui::layout;
+use rltk::{Console, Point, Rltk, RGB};
+use specs;
+use specs::prelude::*;
+
+/// Cuts a line down to fit the panel.
+fn fit(text: &str, width: i32) -> String {
+    text.chars().take(i32::max(0, width) as usize).collect()
+}
+
+/// Items the player can see that are on screen, nearest first.
+fn visible_items(ecs: &World) -> Vec<Entity> {
+    let player_entity = *ecs.fetch::<Entity>();
+    let player_pos = *ecs.fetch::<Point>();
+    let camera = camera::Camera::new(ecs);
+    let viewsheds = ecs.read_storage::<components::Viewshed>();
+    let items = ecs.read_storage::<components::Item>();
+    let positions = ecs.read_storage::<components::Position>();
+    let entities = ecs.entities();
+
+    let viewshed = match viewsheds.get(player_entity) {
+        Some(viewshed) => viewshed,
+        None => return Vec::new(),
+    };
+    let mut found: Vec<(Entity, f32)> = (&entities, &items, &positions)
+        .join()
+        .map(|(entity, _item, pos)| (entity, Point::new(pos.x, pos.y)))
+        .filter(|(_, pos)| camera.to_screen(*pos).is_some())
+        .filter(|(_, pos)| viewshed.visible_tiles.contains(pos))
+        .map(|(entity, pos)| {
+            let distance = rltk::DistanceAlg::Pythagoras.distance2d(player_pos, pos);
+            (entity, distance)
+        })
+        .collect();
+    found.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    found.into_iter().map(|(entity, _)| entity).collect()
+}
+
+/// Lists the hostiles in the player's sight, with their health and what's
+/// ailing them, then the items in view. Whatever doesn't fit is left off.
+pub fn draw(ecs: &World, ctx: &mut Rltk) {
+    let gui = ecs.fetch::<GUI>();
+    let panel = match layout::Layout::new(&gui).sidebar {
+        Some(panel) => panel,
+        None => return,
+    };
+    let player_entity = *ecs.fetch::<Entity>();
+    let names = ecs.read_storage::<components::Name>();
+    let renderables = ecs.read_storage::<components::Renderable>();
+    let combat_stats = ecs.read_storage::<components::CombatStats>();
+    let statuses = ecs.read_storage::<components::StatusEffects>();
+
+    let fg = RGB::named(gui.fg_color);
+    let bg = RGB::named(gui.bg_color);
+    ctx.draw_box(
+        panel.x1,
+        panel.y1,
+        panel.x2 - panel.x1,
+        panel.y2 - panel.y1,
+        fg,
+        bg,
+    );
+    ctx.print_color(
+        panel.x1 + 2,
+        panel.y1,
+        RGB::named(gui.theme.title),
+        bg,
+        "In View",
+    );
+
+    let x = panel.x1 + 2;
+    let width = panel.x2 - x - 1;
+    let last_row = panel.y2 - 1;
+    let mut y = panel.y1 + 2;
+
+    let name_of = |entity: Entity| {
+        names
+            .get(entity)
+            .map_or("something".to_string(), |name| name.name.clone())
+    };
+    let glyph = |ctx: &mut Rltk, entity: Entity, y: i32| {
+        if let Some(render) = renderables.get(entity) {
+            ctx.set(x, y, render.fg, bg, render.glyph);
+        }
+    };
+
+    let hostiles = faction::visible_hostiles(ecs, player_entity);
+    ctx.print_color(x, y, RGB::named(gui.theme.heading), bg, "Hostiles");
+    y += 1;
+    if hostiles.is_empty() && y <= last_row {
+        ctx.print_color(x, y, RGB::named(gui.theme.muted), bg, "none");
+        y += 1;
+    }
+    for hostile in hostiles {
+        if y + 1 > last_row {
+            break;
+        }
+        glyph(ctx, hostile, y);
+        ctx.print_color(x + 2, y, fg, bg, &fit(&name_of(hostile), width - 2));
+        y += 1;
+        if let Some(stats) = combat_stats.get(hostile) {
+            ctx.draw_bar_horizontal(
+                x + 2,
+                y,
+                width - 2,
+                stats.hp,
+                stats.max_hp,
+                RGB::named(gui.theme.health),
+                bg,
+            );
+            y += 1;
+        }
+        let effects = statuses
+            .get(hostile)
+            .map(|afflicted| afflicted.describe())
+            .filter(|effects| !effects.is_empty());
+        if let Some(effects) = effects {
+            if y <= last_row {
+                ctx.print_color(
+                    x + 2,
+                    y,
+                    RGB::named(gui.theme.effects),
+                    bg,
+                    &fit(&effects, width - 2),
+                );
+                y += 1;
+            }
+        }
+    }
+
+    y += 1;
+    if y > last_row {
+        return;
+    }
+    ctx.print_color(x, y, RGB::named(gui.theme.heading), bg, "Items");
+    y += 1;
+    let items = visible_items(ecs);
+    if items.is_empty() && y <= last_row {
+        ctx.print_color(x, y, RGB::named(gui.theme.muted), bg, "none");
+    }
+    for item in items {
+        if y > last_row {
+            break;
+        }
+        glyph(ctx, item, y);
+        ctx.print_color(x + 2, y, fg, bg, &fit(&name_of(item), width - 2));
+        y += 1;
+    }
+}
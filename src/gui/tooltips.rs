@@ -1,96 +1,85 @@
 use crate::components;
+use crate::gui;
+use crate::gui::camera;
+use crate::gui::layout;
 use crate::map;
 use rltk::{Console, Point, Rltk, RGB};
 use specs;
 use specs::prelude::*;
 
-pub fn draw(ecs: &World, ctx: &mut Rltk) {
+/// Names everything the player can see on a tile, along with any effects
+/// they're under.
+pub fn describe(ecs: &World, x: i32, y: i32) -> Vec<String> {
     let map = ecs.fetch::<map::Map>();
     let names = ecs.read_storage::<components::Name>();
     let positions = ecs.read_storage::<components::Position>();
+    let statuses = ecs.read_storage::<components::StatusEffects>();
 
-    let mouse_pos = ctx.mouse_pos();
-    if mouse_pos.0 >= map.width || mouse_pos.1 >= map.height {
-        return;
-    }
     let mut tooltip: Vec<String> = Vec::new();
-    for (name, position) in (&names, &positions).join() {
-        if position.x == mouse_pos.0 && position.y == mouse_pos.1 {
-            tooltip.push(name.name.to_string());
+    if x < 0 || y < 0 || x >= map.width || y >= map.height {
+        return tooltip;
+    }
+    if !map.visible_tiles[map.xy_idx(x, y)] {
+        return tooltip;
+    }
+    for (name, position, afflicted) in (&names, &positions, statuses.maybe()).join() {
+        if position.x == x && position.y == y {
+            match afflicted {
+                None => tooltip.push(name.name.to_string()),
+                Some(afflicted) => {
+                    tooltip.push(format!("{} ({})", name.name, afflicted.describe()))
+                }
+            }
         }
     }
+    tooltip
+}
+
+pub fn draw(ecs: &World, ctx: &mut Rltk) {
+    let camera = camera::Camera::new(ecs);
+    if let Some(pos) = camera.mouse(ctx) {
+        let tooltip = describe(ecs, pos.x, pos.y);
+        let (x, y) = ctx.mouse_pos();
+        draw_at(ecs, ctx, Point::new(x, y), &tooltip);
+    }
+}
+
+/// Draws a tooltip pointing at a spot on screen, on whichever side of it
+/// has room.
+pub fn draw_at(ecs: &World, ctx: &mut Rltk, pos: Point, tooltip: &[String]) {
+    if tooltip.is_empty() {
+        return;
+    }
+    let gui = ecs.fetch::<gui::GUI>();
+    let fg = RGB::named(gui.theme.tooltip_fg);
+    let bg = RGB::named(gui.theme.tooltip_bg);
+    let width = tooltip.iter().map(|s| s.len() as i32).max().unwrap_or(0) + 3;
 
-    if !tooltip.is_empty() {
-        let mut width: i32 = 0;
+    if layout::Layout::new(&gui).tooltip_on_left(pos) {
+        let arrow_pos = Point::new(pos.x - 2, pos.y);
+        let left_x = pos.x - width;
+        let mut y = pos.y;
         for s in tooltip.iter() {
-            if width < s.len() as i32 {
-                width = s.len() as i32;
+            ctx.print_color(left_x, y, fg, bg, s);
+            let padding = (width - s.len() as i32) - 1;
+            for i in 0..padding {
+                ctx.print_color(arrow_pos.x - i, y, fg, bg, &" ".to_string());
             }
+            y += 1;
         }
-        width += 3;
-
-        if mouse_pos.0 > 40 {
-            let arrow_pos = Point::new(mouse_pos.0 - 2, mouse_pos.1);
-            let left_x = mouse_pos.0 - width;
-            let mut y = mouse_pos.1;
-            for s in tooltip.iter() {
-                ctx.print_color(
-                    left_x,
-                    y,
-                    RGB::named(rltk::BLACK),
-                    RGB::named(rltk::GREY),
-                    s,
-                );
-                let padding = (width - s.len() as i32) - 1;
-                for i in 0..padding {
-                    ctx.print_color(
-                        arrow_pos.x - i,
-                        y,
-                        RGB::named(rltk::BLACK),
-                        RGB::named(rltk::GREY),
-                        &" ".to_string(),
-                    );
-                }
-                y += 1;
-            }
-            ctx.print_color(
-                arrow_pos.x,
-                arrow_pos.y,
-                RGB::named(rltk::BLACK),
-                RGB::named(rltk::GREY),
-                &"->".to_string(),
-            );
-        } else {
-            let arrow_pos = Point::new(mouse_pos.0 + 1, mouse_pos.1);
-            let left_x = mouse_pos.0 + 3;
-            let mut y = mouse_pos.1;
-            for s in tooltip.iter() {
-                ctx.print_color(
-                    left_x + 1,
-                    y,
-                    RGB::named(rltk::BLACK),
-                    RGB::named(rltk::GREY),
-                    s,
-                );
-                let padding = (width - s.len() as i32) - 1;
-                for i in 0..padding {
-                    ctx.print_color(
-                        arrow_pos.x + 1 + i,
-                        y,
-                        RGB::named(rltk::BLACK),
-                        RGB::named(rltk::GREY),
-                        &" ".to_string(),
-                    );
-                }
-                y += 1;
+        ctx.print_color(arrow_pos.x, arrow_pos.y, fg, bg, &"->".to_string());
+    } else {
+        let arrow_pos = Point::new(pos.x + 1, pos.y);
+        let left_x = pos.x + 3;
+        let mut y = pos.y;
+        for s in tooltip.iter() {
+            ctx.print_color(left_x + 1, y, fg, bg, s);
+            let padding = (width - s.len() as i32) - 1;
+            for i in 0..padding {
+                ctx.print_color(arrow_pos.x + 1 + i, y, fg, bg, &" ".to_string());
             }
-            ctx.print_color(
-                arrow_pos.x,
-                arrow_pos.y,
-                RGB::named(rltk::BLACK),
-                RGB::named(rltk::GREY),
-                &"<-".to_string(),
-            );
+            y += 1;
         }
+        ctx.print_color(arrow_pos.x, arrow_pos.y, fg, bg, &"<-".to_string());
     }
 }
@@ -0,0 +1,128 @@
+use crate::combat::damage::DamageType;
+use crate::combat::dice;
+use crate::components;
+use crate::config;
+use log;
+use rltk;
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EquipmentSlot {
+    Melee,
+    Shield,
+    Armour,
+    Helmet,
+    Ring,
+}
+
+impl EquipmentSlot {
+    pub fn all() -> Vec<EquipmentSlot> {
+        vec![
+            EquipmentSlot::Melee,
+            EquipmentSlot::Shield,
+            EquipmentSlot::Armour,
+            EquipmentSlot::Helmet,
+            EquipmentSlot::Ring,
+        ]
+    }
+
+    /// How many items can be worn in the slot at once (one ring per hand).
+    pub fn capacity(self) -> usize {
+        match self {
+            EquipmentSlot::Ring => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for EquipmentSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            EquipmentSlot::Melee => "Weapon",
+            EquipmentSlot::Shield => "Shield",
+            EquipmentSlot::Armour => "Armour",
+            EquipmentSlot::Helmet => "Helmet",
+            EquipmentSlot::Ring => "Ring",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Everything an entity's worn equipment adds up to. A worn weapon replaces
+/// the wearer's natural attack.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Totals {
+    pub power: i32,
+    pub defense: i32,
+    pub absorb: i32,
+    pub damage: Option<dice::Dice>,
+    pub damage_type: Option<DamageType>,
+}
+
+pub fn bonuses(
+    owner: Entity,
+    equipped: &ReadStorage<components::Equipped>,
+    bonuses: &ReadStorage<components::EquipmentBonus>,
+) -> Totals {
+    let mut totals = Totals::default();
+    for (worn, bonus) in (equipped, bonuses).join() {
+        if worn.owner != owner {
+            continue;
+        }
+        totals.power += bonus.power;
+        totals.defense += bonus.defense;
+        totals.absorb += bonus.absorb;
+        if bonus.damage.is_some() {
+            totals.damage = bonus.damage;
+            totals.damage_type = bonus.damage_type;
+        }
+    }
+    totals
+}
+
+pub fn random(ecs: &mut World, start: components::Position, cfg: &config::Items) -> Entity {
+    let roll: i32;
+    {
+        let mut rng = ecs.write_resource::<rltk::RandomNumberGenerator>();
+        roll = rng.roll_dice(1, 6);
+    }
+    match roll {
+        1 => spawn(ecs, start, &cfg.dagger),
+        2 => spawn(ecs, start, &cfg.longsword),
+        3 => spawn(ecs, start, &cfg.shield),
+        4 => spawn(ecs, start, &cfg.leather_armour),
+        5 => spawn(ecs, start, &cfg.helmet),
+        _ => spawn(ecs, start, &cfg.ring_of_protection),
+    }
+}
+
+pub fn spawn(ecs: &mut World, pos: components::Position, cfg: &config::Item) -> Entity {
+    let item_name = cfg.name.clone();
+    let equipment = cfg.equipment.clone().unwrap();
+    log::trace!("Creating '{}' at {:?} ...", item_name, pos);
+    ecs.create_entity()
+        .with(pos)
+        .with(components::Renderable {
+            glyph: rltk::to_cp437(cfg.chr),
+            fg: rltk::RGB::named(cfg.fg_color),
+            bg: rltk::RGB::named(cfg.bg_color),
+            render_order: 2,
+        })
+        .with(components::Name { name: item_name })
+        .with(components::Item {})
+        .with(components::Weight { weight: cfg.weight })
+        .with(components::Equippable {
+            slot: equipment.slot,
+        })
+        .with(components::EquipmentBonus {
+            power: equipment.power,
+            defense: equipment.defense,
+            absorb: equipment.absorb,
+            damage: equipment.damage,
+            damage_type: equipment.damage_type,
+        })
+        .build()
+}
@@ -0,0 +1,26 @@
+use crate::components;
+use crate::config;
+use log;
+use rltk;
+use specs::prelude::*;
+
+pub fn spawn_food(ecs: &mut World, pos: components::Position, cfg: &config::Item) -> Entity {
+    let food_name = cfg.name.clone();
+    log::trace!("Creating '{}' at {:?} ...", food_name, pos);
+    ecs.create_entity()
+        .with(pos)
+        .with(components::Renderable {
+            glyph: rltk::to_cp437(cfg.chr),
+            fg: rltk::RGB::named(cfg.fg_color),
+            bg: rltk::RGB::named(cfg.bg_color),
+            render_order: 2,
+        })
+        .with(components::Name { name: food_name })
+        .with(components::Item {})
+        .with(components::Weight { weight: cfg.weight })
+        .with(components::Consumable {})
+        .with(components::ProvidesFood {
+            nutrition: cfg.nutrition.unwrap(),
+        })
+        .build()
+}
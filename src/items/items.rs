@@ -1,20 +1,119 @@
 use crate::components;
 use crate::config;
+use crate::items::equipment;
+use crate::items::food;
 use crate::items::potions;
 use crate::items::scrolls;
 use rltk;
 use specs::prelude::*;
+use std::fmt;
 
 pub fn random(ecs: &mut World, start: components::Position, cfg: &config::Items) {
     let roll: i32;
     {
         let mut rng = ecs.write_resource::<rltk::RandomNumberGenerator>();
-        roll = rng.roll_dice(1, 5);
+        roll = rng.roll_dice(1, 9);
     }
     match roll {
         1 => scrolls::spawn_ranged(ecs, start, &cfg.magic_missile_scroll),
         2 => scrolls::spawn_ranged_aoe(ecs, start, &cfg.fireball_scroll),
-        3 => scrolls::spawn_ranged_confusion(ecs, start, &cfg.confusion_scroll),
+        3 => scrolls::spawn_ranged_status(ecs, start, &cfg.confusion_scroll),
+        4 => potions::spawn_status_potion(ecs, start, &cfg.haste_potion),
+        5 => equipment::random(ecs, start, cfg),
+        6 | 7 => food::spawn_food(ecs, start, &cfg.ration),
         _ => potions::spawn_health_potion(ecs, start, &cfg.health_potion),
+    };
+}
+
+/// Spawns an item by its key in the items config, e.g. "health_potion". What
+/// gets spawned depends on the fields the item is given there; keys that
+/// aren't in the config, or that don't describe any kind of item, spawn
+/// nothing.
+pub fn spawn_by_key(
+    ecs: &mut World,
+    pos: components::Position,
+    key: &str,
+    cfg: &config::Items,
+) -> Option<Entity> {
+    let item = match cfg.get(key) {
+        None => {
+            log::warn!("There's no item called '{}' in the config", key);
+            return None;
+        }
+        Some(item) => item,
+    };
+    let entity = if item.equipment.is_some() {
+        equipment::spawn(ecs, pos, item)
+    } else if item.nutrition.is_some() {
+        food::spawn_food(ecs, pos, item)
+    } else if item.range.is_some() && item.radius.is_some() {
+        scrolls::spawn_ranged_aoe(ecs, pos, item)
+    } else if item.range.is_some() && item.effect.is_some() {
+        scrolls::spawn_ranged_status(ecs, pos, item)
+    } else if item.range.is_some() {
+        scrolls::spawn_ranged(ecs, pos, item)
+    } else if item.effect.is_some() {
+        potions::spawn_status_potion(ecs, pos, item)
+    } else if item.power > 0 {
+        potions::spawn_health_potion(ecs, pos, item)
+    } else {
+        log::warn!("The item '{}' in the config can't be spawned", key);
+        return None;
+    };
+    Some(entity)
+}
+
+/// The kinds of item the inventory is sorted into, in the order they're
+/// listed.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Category {
+    Equipment,
+    Potion,
+    Scroll,
+    Food,
+    Other,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Category::Equipment => "Equipment",
+            Category::Potion => "Potions",
+            Category::Scroll => "Scrolls",
+            Category::Food => "Food",
+            Category::Other => "Other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Works out which kind of item something is from what it does.
+pub fn category(ecs: &World, item: Entity) -> Category {
+    if ecs
+        .read_storage::<components::Equippable>()
+        .get(item)
+        .is_some()
+    {
+        Category::Equipment
+    } else if ecs
+        .read_storage::<components::ProvidesFood>()
+        .get(item)
+        .is_some()
+    {
+        Category::Food
+    } else if ecs.read_storage::<components::Ranged>().get(item).is_some() {
+        Category::Scroll
+    } else if ecs
+        .read_storage::<components::ProvidesHealing>()
+        .get(item)
+        .is_some()
+        || ecs
+            .read_storage::<components::InflictsStatus>()
+            .get(item)
+            .is_some()
+    {
+        Category::Potion
+    } else {
+        Category::Other
     }
 }
@@ -1,3 +1,5 @@
+pub mod equipment;
+pub mod food;
 pub mod items;
 pub mod potions;
 pub mod scrolls;
@@ -4,7 +4,7 @@ use log;
 use rltk;
 use specs::prelude::*;
 
-pub fn spawn_ranged(ecs: &mut World, pos: components::Position, cfg: &config::Item) {
+pub fn spawn_ranged(ecs: &mut World, pos: components::Position, cfg: &config::Item) -> Entity {
     let scroll_name = cfg.name.clone();
     log::trace!("Creating '{}' at {:?} ...", scroll_name, pos);
     ecs.create_entity()
@@ -17,18 +17,23 @@ pub fn spawn_ranged(ecs: &mut World, pos: components::Position, cfg: &config::It
         })
         .with(components::Name { name: scroll_name })
         .with(components::Item {})
+        .with(components::Weight { weight: cfg.weight })
         .with(components::Consumable {})
         .with(components::Ranged {
             range: cfg.range.unwrap(),
         })
-        .with(components::InflictsDamage { damage: cfg.power })
-        .build();
+        .with(components::InflictsDamage {
+            damage: cfg.power,
+            damage_type: cfg.damage_type.unwrap_or_default(),
+        })
+        .build()
 }
 
-pub fn spawn_ranged_aoe(ecs: &mut World, pos: components::Position, cfg: &config::Item) {
+pub fn spawn_ranged_aoe(ecs: &mut World, pos: components::Position, cfg: &config::Item) -> Entity {
     let scroll_name = cfg.name.clone();
     log::trace!("Creating '{}' at {:?} ...", scroll_name, pos);
-    ecs.create_entity()
+    let mut builder = ecs
+        .create_entity()
         .with(pos)
         .with(components::Renderable {
             glyph: rltk::to_cp437(cfg.chr),
@@ -38,18 +43,30 @@ pub fn spawn_ranged_aoe(ecs: &mut World, pos: components::Position, cfg: &config
         })
         .with(components::Name { name: scroll_name })
         .with(components::Item {})
+        .with(components::Weight { weight: cfg.weight })
         .with(components::Consumable {})
         .with(components::Ranged {
             range: cfg.range.unwrap(),
         })
-        .with(components::InflictsDamage { damage: cfg.power })
+        .with(components::InflictsDamage {
+            damage: cfg.power,
+            damage_type: cfg.damage_type.unwrap_or_default(),
+        })
         .with(components::AreaOfEffect {
             radius: cfg.radius.unwrap(),
-        })
-        .build();
+        });
+    // Some blasts leave something behind, e.g. burning
+    if let Some(effect) = cfg.effect {
+        builder = builder.with(components::InflictsStatus { effect });
+    }
+    builder.build()
 }
 
-pub fn spawn_ranged_confusion(ecs: &mut World, pos: components::Position, cfg: &config::Item) {
+pub fn spawn_ranged_status(
+    ecs: &mut World,
+    pos: components::Position,
+    cfg: &config::Item,
+) -> Entity {
     let scroll_name = cfg.name.clone();
     log::trace!("Creating '{}' at {:?} ...", scroll_name, pos);
     ecs.create_entity()
@@ -62,10 +79,13 @@ pub fn spawn_ranged_confusion(ecs: &mut World, pos: components::Position, cfg: &
         })
         .with(components::Name { name: scroll_name })
         .with(components::Item {})
+        .with(components::Weight { weight: cfg.weight })
         .with(components::Consumable {})
         .with(components::Ranged {
             range: cfg.range.unwrap(),
         })
-        .with(components::Confusion { turns: cfg.power })
-        .build();
+        .with(components::InflictsStatus {
+            effect: cfg.effect.unwrap(),
+        })
+        .build()
 }
@@ -4,6 +4,8 @@ pub use self::ai::*;
 pub use self::combat::*;
 pub use self::components::*;
 pub use self::config::*;
+pub use self::effects::*;
+pub use self::faction::*;
 pub use self::game::*;
 pub use self::gui::*;
 pub use self::items::*;
@@ -19,6 +21,8 @@ pub mod ai;
 pub mod combat;
 pub mod components;
 pub mod config;
+pub mod effects;
+pub mod faction;
 pub mod game;
 pub mod gui;
 pub mod items;
@@ -2,7 +2,6 @@ use hxgm30client::config;
 use hxgm30client::game;
 use hxgm30client::gui;
 use hxgm30client::logger;
-use specs::prelude::*;
 
 fn main() {
     let cfg = config::AppConfig::new();
@@ -17,9 +16,7 @@ fn main() {
         .with_title(title)
         .with_fullscreen(cfg.gui.fullscreen)
         .build();
-    let mut gs = game::state::State {
-        ecs: specs::World::new(),
-    };
+    let mut gs = game::state::State::new();
 
     game::world::setup(cfg, &mut gs);
 
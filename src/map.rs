@@ -1,6 +1,7 @@
 use crate::components;
 use crate::config;
 use crate::game;
+use crate::gui::camera;
 use crate::rect;
 use crate::rooms;
 use rltk::{Algorithm2D, BaseMap, Console, Point, RandomNumberGenerator, Rltk, RGB};
@@ -106,14 +107,14 @@ impl Map {
     /// Makes a new map using the algorithm from http://rogueliketutorials.com/tutorials/tcod/part-3/
     /// This gives a handful of random rooms and corridors joining them together.
     pub fn new_map_rooms_and_corridors(cfg: &config::AppConfig, map_depth: i32) -> Map {
-        let width = cfg.gui.map_area.width;
-        let height = cfg.gui.map_area.height;
+        let width = cfg.map.width;
+        let height = cfg.map.height;
         let tile_count = width * height;
         let mut map = Map {
             tiles: vec![TileType::Wall; tile_count as usize],
             rooms: Vec::new(),
-            width: cfg.gui.map_area.width,
-            height: cfg.gui.map_area.height,
+            width,
+            height,
             revealed_tiles: vec![false; tile_count as usize],
             visible_tiles: vec![false; tile_count as usize],
             blocked: vec![false; tile_count as usize],
@@ -202,6 +203,13 @@ impl BaseMap for Map {
 
         exits
     }
+
+    fn get_pathing_distance(&self, idx1: usize, idx2: usize) -> f32 {
+        let (idx1, idx2) = (idx1 as i32, idx2 as i32);
+        let p1 = Point::new(idx1 % self.width, idx1 / self.width);
+        let p2 = Point::new(idx2 % self.width, idx2 / self.width);
+        rltk::DistanceAlg::Pythagoras.distance2d(p1, p2)
+    }
 }
 
 impl Algorithm2D for Map {
@@ -210,19 +218,58 @@ impl Algorithm2D for Map {
     }
 }
 
+/// The map as far as the player knows it: only revealed tiles can be walked
+/// on, so that automatic movement doesn't give away what's still unexplored.
+pub struct RevealedMap<'a> {
+    pub map: &'a Map,
+}
+
+impl<'a> BaseMap for RevealedMap<'a> {
+    fn is_opaque(&self, idx: usize) -> bool {
+        self.map.is_opaque(idx)
+    }
+
+    fn get_available_exits(&self, idx: usize) -> Vec<(usize, f32)> {
+        self.map
+            .get_available_exits(idx)
+            .into_iter()
+            .filter(|(exit, _)| self.map.revealed_tiles[*exit])
+            .collect()
+    }
+
+    fn get_pathing_distance(&self, idx1: usize, idx2: usize) -> f32 {
+        self.map.get_pathing_distance(idx1, idx2)
+    }
+}
+
+impl<'a> Algorithm2D for RevealedMap<'a> {
+    fn dimensions(&self) -> Point {
+        self.map.dimensions()
+    }
+}
+
+/// Draws the part of the map the camera is looking at.
 pub fn draw(ecs: &specs::World, ctx: &mut Rltk) {
     let game_map = ecs.fetch::<Map>();
+    let camera = camera::Camera::new(ecs);
+    let app_cfg = ecs.fetch::<config::AppConfig>();
+    let cfg = &app_cfg.map;
+
+    for y in 0..camera.height {
+        for x in 0..camera.width {
+            let pos = match camera.to_world(x, y) {
+                None => continue,
+                Some(pos) => pos,
+            };
+            let idx = game_map.xy_idx(pos.x, pos.y);
+            if !game_map.revealed_tiles[idx] {
+                continue;
+            }
 
-    let mut y = 0;
-    let mut x = 0;
-    for (idx, tile) in game_map.tiles.iter().enumerate() {
-        // Render a tile depending upon the tile type
-
-        if game_map.revealed_tiles[idx] {
-            let cfg = ecs.fetch::<config::Map>();
+            // Render a tile depending upon the tile type
             let glyph;
             let mut fg;
-            match tile {
+            match game_map.tiles[idx] {
                 TileType::Floor => {
                     glyph = rltk::to_cp437(cfg.floor.chr.unwrap());
                     fg = RGB::named(cfg.floor.fg_color);
@@ -253,13 +300,6 @@ pub fn draw(ecs: &specs::World, ctx: &mut Rltk) {
             }
             ctx.set(x, y, fg, RGB::named(cfg.default.fg_color), glyph);
         }
-
-        // Move the coordinates
-        x += 1;
-        if x > (game_map.width - 1) {
-            x = 0;
-            y += 1;
-        }
     }
 }
 
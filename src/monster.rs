@@ -1,4 +1,7 @@
-use crate::components::{BlocksTile, CombatStats, Monster, Name, Position, Renderable, Viewshed};
+use crate::components::{
+    BlocksTile, CombatStats, Energy, ExperienceValue, Faction, Monster, Name, NaturalRegeneration,
+    Position, Renderable, Resistant, Viewshed, Vulnerable,
+};
 use crate::config;
 use rltk::{RandomNumberGenerator, RGB};
 use specs::prelude::*;
@@ -17,7 +20,8 @@ pub fn random(ecs: &mut World, start: Position, cfg: &config::Monsters) {
 
 pub fn spawn(ecs: &mut World, start: Position, cfg: &config::Monsters, m: &config::Monster) {
     log::trace!("Creating monster at {:?} ...", start);
-    ecs.create_entity()
+    let mut builder = ecs
+        .create_entity()
         .with(start)
         .with(Renderable {
             glyph: rltk::to_cp437(m.chr),
@@ -40,6 +44,29 @@ pub fn spawn(ecs: &mut World, start: Position, cfg: &config::Monsters, m: &confi
             hp: m.stats.starting_hp,
             defense: m.stats.defense,
             power: m.stats.power,
+            damage: m.stats.damage,
+            damage_type: m.stats.damage_type,
         })
-        .build();
+        .with(Energy {
+            speed: m.speed,
+            current: 0,
+        })
+        .with(Faction {
+            name: m.faction.clone(),
+        })
+        .with(ExperienceValue { xp: m.xp });
+    if !m.resistances.is_empty() {
+        builder = builder.with(Resistant {
+            damage_types: m.resistances.clone(),
+        });
+    }
+    if !m.vulnerabilities.is_empty() {
+        builder = builder.with(Vulnerable {
+            damage_types: m.vulnerabilities.clone(),
+        });
+    }
+    if let Some(regen) = m.regeneration {
+        builder = builder.with(NaturalRegeneration::new(regen.interval, regen.amount));
+    }
+    builder.build();
 }
@@ -1,4 +1,5 @@
 use crate::components;
+use crate::effects::status::StatusKind;
 use crate::map;
 use rltk::{field_of_view, Point};
 use specs::prelude::*;
@@ -11,19 +12,24 @@ impl<'a> System<'a> for VisibilitySystem {
         WriteExpect<'a, map::Map>,
         Entities<'a>,
         WriteStorage<'a, components::Viewshed>,
-        WriteStorage<'a, components::Position>,
+        ReadStorage<'a, components::Position>,
         ReadStorage<'a, components::Player>,
+        ReadStorage<'a, components::StatusEffects>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut map, entities, mut viewshed, pos, player) = data;
+        let (mut map, entities, mut viewshed, pos, player, statuses) = data;
 
         for (ent, viewshed, pos) in (&entities, &mut viewshed, &pos).join() {
             if viewshed.dirty {
                 viewshed.dirty = false;
                 viewshed.visible_tiles.clear();
-                viewshed.visible_tiles =
-                    field_of_view(Point::new(pos.x, pos.y), viewshed.range, &*map);
+                // The blind can only feel their way around
+                let blind = statuses
+                    .get(ent)
+                    .map_or(false, |s| s.has(StatusKind::Blindness));
+                let range = if blind { 1 } else { viewshed.range };
+                viewshed.visible_tiles = field_of_view(Point::new(pos.x, pos.y), range, &*map);
                 viewshed
                     .visible_tiles
                     .retain(|p| p.x > 0 && p.x < map.width - 1 && p.y > 0 && p.y < map.height - 1);
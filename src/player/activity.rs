@@ -0,0 +1,239 @@
+use crate::components;
+use crate::faction;
+use crate::game;
+use crate::game::log::Category;
+use crate::game::turns;
+use crate::map;
+use crate::player::explore;
+use crate::player::user;
+use rltk::Point;
+use specs::prelude::*;
+
+/// Something the player keeps on doing, one turn at a time, until it's done
+/// or something interrupts it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Activity {
+    Resting,
+    Exploring,
+    Travelling { destination: usize },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AutoActivity {
+    pub current: Option<Activity>,
+    /// The player's HP as of the last step, so that damage can be noticed
+    pub last_hp: i32,
+    /// Items the player already knew about, so that only new finds stop them
+    pub seen_items: Vec<Entity>,
+}
+
+fn player_hp(ecs: &World) -> (i32, i32) {
+    let player_entity = ecs.fetch::<Entity>();
+    let combat_stats = ecs.read_storage::<components::CombatStats>();
+    combat_stats
+        .get(*player_entity)
+        .map_or((0, 0), |stats| (stats.hp, stats.max_hp))
+}
+
+/// Why resting won't do the player any good, if it won't: they must heal
+/// naturally and not be too hungry to.
+fn cannot_heal(ecs: &World) -> Option<&'static str> {
+    let player_entity = ecs.fetch::<Entity>();
+    let regenerates = ecs
+        .read_storage::<components::NaturalRegeneration>()
+        .get(*player_entity)
+        .is_some();
+    let starved = ecs
+        .read_storage::<components::Hunger>()
+        .get(*player_entity)
+        .map_or(false, |hunger| hunger.state.stops_healing());
+    if !regenerates {
+        Some("Resting won't heal you.")
+    } else if starved {
+        Some("You're too hungry to recover by resting.")
+    } else {
+        None
+    }
+}
+
+fn visible_items(ecs: &World) -> Vec<Entity> {
+    let game_map = ecs.fetch::<map::Map>();
+    let entities = ecs.entities();
+    let items = ecs.read_storage::<components::Item>();
+    let positions = ecs.read_storage::<components::Position>();
+    (&entities, &items, &positions)
+        .join()
+        .filter(|(_, _, pos)| game_map.visible_tiles[game_map.xy_idx(pos.x, pos.y)])
+        .map(|(entity, _, _)| entity)
+        .collect()
+}
+
+fn log(ecs: &World, message: &str) {
+    let mut gamelog = ecs.fetch_mut::<game::log::GameLog>();
+    gamelog.add(Category::System, message);
+}
+
+/// Takes a step, giving up on the activity if the way is blocked.
+fn walk(ecs: &mut World, dx: i32, dy: i32) -> Option<turns::Action> {
+    let action = user::try_move(dx, dy, ecs);
+    if action.is_none() {
+        ecs.fetch_mut::<AutoActivity>().current = None;
+    }
+    action
+}
+
+pub fn is_active(ecs: &World) -> bool {
+    ecs.fetch::<AutoActivity>().current.is_some()
+}
+
+/// Starts an activity, unless there's a hostile in view.
+pub fn start(ecs: &World, activity: Activity) {
+    let player_entity = *ecs.fetch::<Entity>();
+    if !faction::visible_hostiles(ecs, player_entity).is_empty() {
+        log(ecs, "You can't do that with enemies nearby.");
+        return;
+    }
+    if activity == Activity::Resting {
+        let (hp, max_hp) = player_hp(ecs);
+        if hp >= max_hp {
+            log(ecs, "You don't need to rest.");
+            return;
+        }
+        log(ecs, "You rest for a while ...");
+    }
+    let (hp, _) = player_hp(ecs);
+    let seen_items = visible_items(ecs);
+    let mut auto = ecs.fetch_mut::<AutoActivity>();
+    auto.current = Some(activity);
+    auto.last_hp = hp;
+    auto.seen_items = seen_items;
+}
+
+/// Heads for a tile the player has seen, if it's somewhere one can stand.
+pub fn travel_to(ecs: &World, target: Point) {
+    let destination = {
+        let game_map = ecs.fetch::<map::Map>();
+        if target.x < 1
+            || target.y < 1
+            || target.x > game_map.width - 2
+            || target.y > game_map.height - 2
+        {
+            return;
+        }
+        let idx = game_map.xy_idx(target.x, target.y);
+        if !game_map.revealed_tiles[idx] || game_map.tiles[idx] == map::TileType::Wall {
+            drop(game_map);
+            log(ecs, "You don't know of a way there.");
+            return;
+        }
+        idx
+    };
+    start(ecs, Activity::Travelling { destination });
+}
+
+/// Heads for the way down, if it's been found.
+pub fn travel_to_exit(ecs: &World) {
+    let destination = {
+        let game_map = ecs.fetch::<map::Map>();
+        (0..game_map.tiles.len()).find(|idx| {
+            game_map.revealed_tiles[*idx]
+                && game_map.tiles[*idx] == map::TileType::Exit(map::ExitDirection::Down)
+        })
+    };
+    match destination {
+        None => log(ecs, "You haven't found a way down yet."),
+        Some(destination) => start(ecs, Activity::Travelling { destination }),
+    }
+}
+
+pub fn stop(ecs: &World, message: &str) {
+    ecs.fetch_mut::<AutoActivity>().current = None;
+    log(ecs, message);
+}
+
+/// Works out the player's next action for the current activity, stopping it
+/// if a hostile has come into view, the player has been hurt or, while on
+/// the move, something new turns up.
+pub fn step(ecs: &mut World) -> Option<turns::Action> {
+    let player_entity = *ecs.fetch::<Entity>();
+    let activity = ecs.fetch::<AutoActivity>().current.clone()?;
+    let (hp, max_hp) = player_hp(ecs);
+
+    if !faction::visible_hostiles(ecs, player_entity).is_empty() {
+        stop(ecs, "You see an enemy and stop.");
+        return None;
+    }
+    if hp < ecs.fetch::<AutoActivity>().last_hp {
+        stop(ecs, "You've been hurt and stop.");
+        return None;
+    }
+    ecs.fetch_mut::<AutoActivity>().last_hp = hp;
+
+    if activity != Activity::Resting {
+        let names = ecs.read_storage::<components::Name>();
+        let found = visible_items(ecs)
+            .into_iter()
+            .find(|item| !ecs.fetch::<AutoActivity>().seen_items.contains(item));
+        if let Some(item) = found {
+            let name = names
+                .get(item)
+                .map_or("something".to_string(), |n| n.name.clone());
+            stop(ecs, &format!("You spot a {}.", name));
+            return None;
+        }
+    }
+
+    match activity {
+        Activity::Resting => {
+            if hp >= max_hp {
+                stop(ecs, "You feel rested.");
+                return None;
+            }
+            if let Some(reason) = cannot_heal(ecs) {
+                stop(ecs, reason);
+                return None;
+            }
+            Some(turns::Action::Wait)
+        }
+        Activity::Exploring => {
+            let from = *ecs.fetch::<Point>();
+            let delta = explore::next_step(&ecs.fetch::<map::Map>(), from);
+            match delta {
+                None => {
+                    stop(ecs, "There's nothing left to explore.");
+                    None
+                }
+                Some((dx, dy)) => walk(ecs, dx, dy),
+            }
+        }
+        Activity::Travelling { destination } => {
+            let from = *ecs.fetch::<Point>();
+            let path = {
+                let game_map = ecs.fetch::<map::Map>();
+                let here = game_map.xy_idx(from.x, from.y);
+                if here == destination {
+                    None
+                } else {
+                    let known = map::RevealedMap { map: &game_map };
+                    Some(rltk::a_star_search(here, destination, &known))
+                }
+            };
+            match path {
+                None => {
+                    stop(ecs, "You have arrived.");
+                    None
+                }
+                Some(path) if !path.success || path.steps.len() < 2 => {
+                    stop(ecs, "You can't find a way there.");
+                    None
+                }
+                Some(path) => {
+                    let width = ecs.fetch::<map::Map>().width;
+                    let x = path.steps[1] as i32 % width;
+                    let y = path.steps[1] as i32 / width;
+                    walk(ecs, x - from.x, y - from.y)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use rltk::VirtualKeyCode;
+
+/// Everything the player can ask for from the map screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    Move(i32, i32),
+    Wait,
+    Rest,
+    Explore,
+    TravelToExit,
+    PickUp,
+    Inventory,
+    Drop,
+    Throw,
+    Equipment,
+    Look,
+    Descend,
+    Character,
+    Messages,
+    Help,
+    Hotbar(usize),
+    AssignHotbar,
+    MainMenu,
+}
+
+impl Command {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Command::Move(-1, 0) => "Move west",
+            Command::Move(1, 0) => "Move east",
+            Command::Move(0, -1) => "Move north",
+            Command::Move(0, 1) => "Move south",
+            Command::Move(-1, -1) => "Move north-west",
+            Command::Move(1, -1) => "Move north-east",
+            Command::Move(1, 1) => "Move south-east",
+            Command::Move(-1, 1) => "Move south-west",
+            Command::Move(_, _) => "Move",
+            Command::Wait => "Wait a turn",
+            Command::Rest => "Rest until healed",
+            Command::Explore => "Explore automatically",
+            Command::TravelToExit => "Travel to the way down",
+            Command::PickUp => "Pick up an item",
+            Command::Inventory => "Use an item",
+            Command::Drop => "Drop an item",
+            Command::Throw => "Throw an item",
+            Command::Equipment => "Show equipment",
+            Command::Look => "Look around",
+            Command::Descend => "Take the way down",
+            Command::Character => "Show character sheet",
+            Command::Messages => "Show message history",
+            Command::Help => "Show this help",
+            Command::Hotbar(_) => "Use the item in a hotbar slot",
+            Command::AssignHotbar => "Put an item in the hotbar",
+            Command::MainMenu => "Main menu",
+        }
+    }
+}
+
+/// A command and the keys that trigger it. Shifted bindings are checked
+/// before unshifted ones, so they can share keys.
+pub struct Binding {
+    pub keys: &'static [VirtualKeyCode],
+    pub shift: bool,
+    pub command: Command,
+}
+
+pub const BINDINGS: &[Binding] = &[
+    Binding {
+        keys: &[
+            VirtualKeyCode::Left,
+            VirtualKeyCode::A,
+            VirtualKeyCode::Key4,
+        ],
+        shift: false,
+        command: Command::Move(-1, 0),
+    },
+    Binding {
+        keys: &[
+            VirtualKeyCode::Right,
+            VirtualKeyCode::D,
+            VirtualKeyCode::Key6,
+        ],
+        shift: false,
+        command: Command::Move(1, 0),
+    },
+    Binding {
+        keys: &[VirtualKeyCode::Up, VirtualKeyCode::W, VirtualKeyCode::Key8],
+        shift: false,
+        command: Command::Move(0, -1),
+    },
+    Binding {
+        keys: &[
+            VirtualKeyCode::Down,
+            VirtualKeyCode::S,
+            VirtualKeyCode::Key2,
+        ],
+        shift: false,
+        command: Command::Move(0, 1),
+    },
+    Binding {
+        keys: &[VirtualKeyCode::Q, VirtualKeyCode::Key7],
+        shift: false,
+        command: Command::Move(-1, -1),
+    },
+    Binding {
+        keys: &[VirtualKeyCode::E, VirtualKeyCode::Key9],
+        shift: false,
+        command: Command::Move(1, -1),
+    },
+    Binding {
+        keys: &[VirtualKeyCode::C, VirtualKeyCode::Key3],
+        shift: false,
+        command: Command::Move(1, 1),
+    },
+    Binding {
+        keys: &[VirtualKeyCode::Z, VirtualKeyCode::Key1],
+        shift: false,
+        command: Command::Move(-1, 1),
+    },
+    Binding {
+        keys: &[VirtualKeyCode::Space, VirtualKeyCode::Key5],
+        shift: false,
+        command: Command::Wait,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::R],
+        shift: false,
+        command: Command::Rest,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::X],
+        shift: false,
+        command: Command::Explore,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::Period],
+        shift: true,
+        command: Command::TravelToExit,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::P],
+        shift: false,
+        command: Command::PickUp,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::I],
+        shift: false,
+        command: Command::Inventory,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::L],
+        shift: false,
+        command: Command::Drop,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::T],
+        shift: false,
+        command: Command::Throw,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::G],
+        shift: false,
+        command: Command::Equipment,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::V],
+        shift: false,
+        command: Command::Look,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::Return],
+        shift: false,
+        command: Command::Descend,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::C],
+        shift: true,
+        command: Command::Character,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::M],
+        shift: false,
+        command: Command::Messages,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::Slash],
+        shift: true,
+        command: Command::Help,
+    },
+    // One key per slot, in slot order
+    Binding {
+        keys: &[
+            VirtualKeyCode::F1,
+            VirtualKeyCode::F2,
+            VirtualKeyCode::F3,
+            VirtualKeyCode::F4,
+            VirtualKeyCode::F5,
+            VirtualKeyCode::F6,
+            VirtualKeyCode::F7,
+            VirtualKeyCode::F8,
+            VirtualKeyCode::F9,
+        ],
+        shift: false,
+        command: Command::Hotbar(0),
+    },
+    Binding {
+        keys: &[VirtualKeyCode::H],
+        shift: false,
+        command: Command::AssignHotbar,
+    },
+    Binding {
+        keys: &[VirtualKeyCode::Escape],
+        shift: false,
+        command: Command::MainMenu,
+    },
+];
+
+/// The command bound to a key, if any. The hotbar keys pick the slot by
+/// where they come in their binding.
+pub fn command(key: VirtualKeyCode, shift: bool) -> Option<Command> {
+    let bound = |b: &&Binding| b.keys.contains(&key);
+    BINDINGS
+        .iter()
+        .filter(bound)
+        .find(|b| b.shift && shift)
+        .or_else(|| BINDINGS.iter().filter(bound).find(|b| !b.shift))
+        .map(|b| match b.command {
+            Command::Hotbar(_) => Command::Hotbar(b.keys.iter().position(|k| *k == key).unwrap()),
+            command => command,
+        })
+}
+
+/// The movement keys: the same ones that move the player also move cursors.
+pub fn direction(key: VirtualKeyCode) -> Option<(i32, i32)> {
+    match command(key, false) {
+        Some(Command::Move(dx, dy)) => Some((dx, dy)),
+        _ => None,
+    }
+}
+
+/// A key as it's written on the keyboard.
+pub fn key_name(key: VirtualKeyCode, shift: bool) -> String {
+    let name = match key {
+        VirtualKeyCode::Key1 => "1".to_string(),
+        VirtualKeyCode::Key2 => "2".to_string(),
+        VirtualKeyCode::Key3 => "3".to_string(),
+        VirtualKeyCode::Key4 => "4".to_string(),
+        VirtualKeyCode::Key5 => "5".to_string(),
+        VirtualKeyCode::Key6 => "6".to_string(),
+        VirtualKeyCode::Key7 => "7".to_string(),
+        VirtualKeyCode::Key8 => "8".to_string(),
+        VirtualKeyCode::Key9 => "9".to_string(),
+        VirtualKeyCode::Key0 => "0".to_string(),
+        VirtualKeyCode::Period if shift => return ">".to_string(),
+        VirtualKeyCode::Slash if shift => return "?".to_string(),
+        VirtualKeyCode::Period => ".".to_string(),
+        VirtualKeyCode::Slash => "/".to_string(),
+        other => format!("{:?}", other),
+    };
+    if shift {
+        format!("Shift+{}", name)
+    } else {
+        name
+    }
+}
+
+/// One line per binding: its keys and what they do. The hotbar's run of
+/// keys is written as a range.
+pub fn describe() -> Vec<(String, &'static str)> {
+    BINDINGS
+        .iter()
+        .map(|b| {
+            let names: Vec<String> = b.keys.iter().map(|key| key_name(*key, b.shift)).collect();
+            let keys = match (b.command, names.first(), names.last()) {
+                (Command::Hotbar(_), Some(first), Some(last)) => format!("{}-{}", first, last),
+                _ => names.join(", "),
+            };
+            (keys, b.command.describe())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shifted_bindings_win_when_shift_is_held() {
+        assert_eq!(command(VirtualKeyCode::C, true), Some(Command::Character));
+        assert_eq!(command(VirtualKeyCode::C, false), Some(Command::Move(1, 1)));
+        assert_eq!(
+            command(VirtualKeyCode::Period, true),
+            Some(Command::TravelToExit)
+        );
+    }
+
+    #[test]
+    fn shift_falls_back_to_the_unshifted_binding() {
+        assert_eq!(command(VirtualKeyCode::I, true), Some(Command::Inventory));
+        assert_eq!(
+            command(VirtualKeyCode::Left, true),
+            Some(Command::Move(-1, 0))
+        );
+    }
+
+    #[test]
+    fn shift_only_bindings_need_shift() {
+        assert_eq!(command(VirtualKeyCode::Period, false), None);
+        assert_eq!(command(VirtualKeyCode::Slash, false), None);
+        assert_eq!(command(VirtualKeyCode::Slash, true), Some(Command::Help));
+    }
+
+    #[test]
+    fn hotbar_keys_pick_their_own_slots() {
+        assert_eq!(command(VirtualKeyCode::F1, false), Some(Command::Hotbar(0)));
+        assert_eq!(command(VirtualKeyCode::F9, false), Some(Command::Hotbar(8)));
+        let rows = describe();
+        let hotbar = rows
+            .iter()
+            .filter(|(_, text)| *text == Command::Hotbar(0).describe());
+        assert_eq!(
+            hotbar.map(|(keys, _)| keys.as_str()).collect::<Vec<_>>(),
+            vec!["F1-F9"]
+        );
+    }
+
+    #[test]
+    fn movement_keys_give_directions() {
+        assert_eq!(direction(VirtualKeyCode::Key7), Some((-1, -1)));
+        assert_eq!(direction(VirtualKeyCode::D), Some((1, 0)));
+        assert_eq!(direction(VirtualKeyCode::I), None);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::components;
 use crate::config;
 use crate::game;
+use crate::game::log::Category;
+use crate::game::turns;
+use crate::gui::menus;
 use crate::map;
 use rltk::{Point, RGB};
 use specs;
@@ -24,13 +27,22 @@ pub fn new(cfg: &config::AppConfig, gs: &mut game::state::State, game_map: &map:
         y: player_y,
       },
       cfg.player.clone(),
+      cfg.turns,
+      cfg.hunger,
     ),
   }
 }
 
-/// Spawns the player and returns his/her entity object.
-pub fn spawn(ecs: &mut World, start: components::Position, cfg: config::Player) -> Entity {
-  ecs
+/// Spawns the player and returns his/her entity object. The player starts with
+/// enough energy to take the first turn.
+pub fn spawn(
+  ecs: &mut World,
+  start: components::Position,
+  cfg: config::Player,
+  turns: config::Turns,
+  hunger: config::Hunger,
+) -> Entity {
+  let mut builder = ecs
     .create_entity()
     .with(start)
     .with(components::Renderable {
@@ -53,11 +65,40 @@ pub fn spawn(ecs: &mut World, start: components::Position, cfg: config::Player)
       hp: cfg.stats.starting_hp,
       defense: cfg.stats.defense,
       power: cfg.stats.power,
+      damage: cfg.stats.damage,
+      damage_type: cfg.stats.damage_type,
     })
-    .build()
+    .with(components::Energy {
+      speed: cfg.speed,
+      current: turns.threshold,
+    })
+    .with(components::Faction {
+      name: cfg.faction.clone(),
+    })
+    .with(components::Experience::new())
+    .with(components::Statistics::default())
+    .with(components::InventoryLetters::default())
+    .with(components::Hunger::well_fed(&hunger));
+  if !cfg.resistances.is_empty() {
+    builder = builder.with(components::Resistant {
+      damage_types: cfg.resistances.clone(),
+    });
+  }
+  if !cfg.vulnerabilities.is_empty() {
+    builder = builder.with(components::Vulnerable {
+      damage_types: cfg.vulnerabilities.clone(),
+    });
+  }
+  if let Some(regen) = cfg.regeneration {
+    builder = builder.with(components::NaturalRegeneration::new(
+      regen.interval,
+      regen.amount,
+    ));
+  }
+  builder.build()
 }
 
-pub fn get_item(ecs: &mut World) {
+pub fn get_item(ecs: &mut World) -> Option<turns::Action> {
   let player_pos = ecs.fetch::<Point>();
   let player_entity = ecs.fetch::<Entity>();
   let entities = ecs.entities();
@@ -73,9 +114,10 @@ pub fn get_item(ecs: &mut World) {
   }
 
   match target_item {
-    None => gamelog
-      .entries
-      .push("There is nothing here to pick up.".to_string()),
+    None => {
+      gamelog.add(Category::Item, "There is nothing here to pick up.");
+      None
+    }
     Some(item) => {
       let mut pickup = ecs.write_storage::<components::WantsToPickupItem>();
       pickup
@@ -87,10 +129,46 @@ pub fn get_item(ecs: &mut World) {
           },
         )
         .expect("Unable to insert want to pickup");
+      Some(turns::Action::PickUp)
     }
   }
 }
 
+/// Puts an item from the pack to use: gear gets worn, ranged items need a
+/// target picked first, and anything else is used up there and then.
+pub fn use_item(ecs: &World, item: Entity) -> game::state::RunState {
+  let player_entity = *ecs.fetch::<Entity>();
+  let range = ecs
+    .read_storage::<components::Ranged>()
+    .get(item)
+    .map(|ranged| ranged.range);
+  let is_equippable = ecs
+    .read_storage::<components::Equippable>()
+    .get(item)
+    .is_some();
+  if is_equippable {
+    ecs
+      .write_storage::<components::WantsToEquipItem>()
+      .insert(player_entity, components::WantsToEquipItem { item })
+      .expect("Unable to insert intent");
+    turns::spend(ecs, player_entity, turns::Action::Equip);
+    game::state::RunState::PlayerTurn
+  } else if let Some(range) = range {
+    menus::target::begin(ecs, range);
+    game::state::RunState::ShowTargeting { range, item }
+  } else {
+    ecs
+      .write_storage::<components::WantsToUseItem>()
+      .insert(
+        player_entity,
+        components::WantsToUseItem { item, target: None },
+      )
+      .expect("Unable to insert intent");
+    turns::spend(ecs, player_entity, turns::Action::UseItem);
+    game::state::RunState::PlayerTurn
+  }
+}
+
 pub fn try_next_level(ecs: &mut World) -> bool {
   let player_pos = ecs.fetch::<Point>();
   let current_level = ecs.fetch::<map::Map>();
@@ -99,9 +177,7 @@ pub fn try_next_level(ecs: &mut World) -> bool {
     true
   } else {
     let mut gamelog = ecs.fetch_mut::<game::log::GameLog>();
-    gamelog
-      .entries
-      .push("There is no way down from here.".to_string());
+    gamelog.add(Category::System, "There is no way down from here.");
     false
   }
 }
@@ -0,0 +1,177 @@
+use crate::components;
+use crate::config;
+use crate::items;
+use crate::player::inventory;
+use rltk::VirtualKeyCode;
+use specs::prelude::*;
+
+pub const MAX_NAME_LENGTH: usize = 20;
+
+/// The name being typed in on the character creation screen.
+#[derive(Clone, Debug, Default)]
+pub struct Draft {
+    pub name: String,
+}
+
+impl Draft {
+    /// Applies a keypress to the name. Returns false for keys that don't
+    /// edit it.
+    pub fn edit(&mut self, key: VirtualKeyCode, shift: bool) -> bool {
+        match key {
+            VirtualKeyCode::Back => {
+                self.name.pop();
+                true
+            }
+            VirtualKeyCode::Space => self.push(' '),
+            VirtualKeyCode::Minus => self.push('-'),
+            VirtualKeyCode::Apostrophe => self.push('\''),
+            _ => {
+                let letter = rltk::letter_to_option(key);
+                if letter < 0 {
+                    return false;
+                }
+                let c = (b'a' + letter as u8) as char;
+                if shift {
+                    self.push(c.to_ascii_uppercase())
+                } else {
+                    self.push(c)
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, c: char) -> bool {
+        if self.name.len() < MAX_NAME_LENGTH {
+            self.name.push(c);
+        }
+        true
+    }
+}
+
+/// Names the player after the draft, falling back to the configured name if
+/// nothing was typed in.
+pub fn rename(ecs: &World) {
+    let cfg = ecs.fetch::<config::AppConfig>();
+    let draft = ecs.fetch::<Draft>();
+    let player_entity = ecs.fetch::<Entity>();
+    let mut names = ecs.write_storage::<components::Name>();
+
+    let name = draft.name.trim();
+    let name = if name.is_empty() {
+        cfg.player.name.clone()
+    } else {
+        name.to_string()
+    };
+    if let Some(player_name) = names.get_mut(*player_entity) {
+        player_name.name = name;
+    }
+}
+
+/// Gives the player a class's stats, eyesight, resistances and kit. Equipment
+/// in the kit is worn straight away while there's room in its slot; everything
+/// else goes into the backpack.
+pub fn apply_class(ecs: &mut World, class: &config::Class) {
+    let cfg = ecs.fetch::<config::AppConfig>().items.clone();
+    let player_entity = *ecs.fetch::<Entity>();
+    {
+        let mut combat_stats = ecs.write_storage::<components::CombatStats>();
+        if let Some(stats) = combat_stats.get_mut(player_entity) {
+            stats.max_hp = class.stats.max_hp;
+            stats.hp = class.stats.starting_hp;
+            stats.defense = class.stats.defense;
+            stats.power = class.stats.power;
+            stats.damage = class.stats.damage;
+            stats.damage_type = class.stats.damage_type;
+        }
+        if let Some(resistances) = &class.resistances {
+            let mut resistant = ecs.write_storage::<components::Resistant>();
+            resistant.remove(player_entity);
+            if !resistances.is_empty() {
+                resistant
+                    .insert(
+                        player_entity,
+                        components::Resistant {
+                            damage_types: resistances.clone(),
+                        },
+                    )
+                    .expect("Unable to insert resistances");
+            }
+        }
+        if let Some(vulnerabilities) = &class.vulnerabilities {
+            let mut vulnerable = ecs.write_storage::<components::Vulnerable>();
+            vulnerable.remove(player_entity);
+            if !vulnerabilities.is_empty() {
+                vulnerable
+                    .insert(
+                        player_entity,
+                        components::Vulnerable {
+                            damage_types: vulnerabilities.clone(),
+                        },
+                    )
+                    .expect("Unable to insert vulnerabilities");
+            }
+        }
+        let mut viewsheds = ecs.write_storage::<components::Viewshed>();
+        if let Some(viewshed) = viewsheds.get_mut(player_entity) {
+            viewshed.range = class.view_range.tile_count;
+            viewshed.dirty = true;
+        }
+    }
+
+    let start = ecs
+        .read_storage::<components::Position>()
+        .get(player_entity)
+        .unwrap()
+        .clone();
+    for key in class.kit.iter() {
+        let item = match items::spawn_by_key(ecs, start.clone(), key, &cfg) {
+            None => continue,
+            Some(item) => item,
+        };
+        ecs.write_storage::<components::Position>().remove(item);
+        // Gear goes on until its slot is full; the rest is packed away
+        let slot = ecs
+            .read_storage::<components::Equippable>()
+            .get(item)
+            .map(|e| e.slot)
+            .filter(|slot| {
+                let worn = ecs
+                    .read_storage::<components::Equipped>()
+                    .join()
+                    .filter(|e| e.owner == player_entity && e.slot == *slot)
+                    .count();
+                worn < slot.capacity()
+            });
+        match slot {
+            Some(slot) => {
+                ecs.write_storage::<components::Equipped>()
+                    .insert(
+                        item,
+                        components::Equipped {
+                            owner: player_entity,
+                            slot,
+                        },
+                    )
+                    .expect("Unable to equip starting kit");
+            }
+            None => {
+                let mut backpack = ecs.write_storage::<components::InBackpack>();
+                backpack
+                    .insert(
+                        item,
+                        components::InBackpack {
+                            owner: player_entity,
+                        },
+                    )
+                    .expect("Unable to pack starting kit");
+                inventory::give_letter(
+                    &mut ecs.write_storage::<components::InventoryLetters>(),
+                    &backpack,
+                    &ecs.read_storage::<components::Name>(),
+                    player_entity,
+                    item,
+                );
+            }
+        }
+    }
+}
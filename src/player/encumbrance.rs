@@ -0,0 +1,142 @@
+use crate::components;
+use crate::config;
+use specs::prelude::*;
+use specs::storage::MaskedStorage;
+use std::fmt;
+use std::ops::Deref;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Encumbrance {
+    Unburdened,
+    Burdened,
+    Overloaded,
+}
+
+impl Encumbrance {
+    /// How weighed down someone carrying `carried` out of `capacity` is.
+    pub fn of(carried: i32, capacity: i32, cfg: &config::Encumbrance) -> Encumbrance {
+        if carried > overload_limit(capacity, cfg) {
+            Encumbrance::Overloaded
+        } else if carried > capacity {
+            Encumbrance::Burdened
+        } else {
+            Encumbrance::Unburdened
+        }
+    }
+
+    /// The energy taken on top of the usual cost of a step.
+    pub fn move_penalty(self, cfg: &config::Encumbrance) -> i32 {
+        match self {
+            Encumbrance::Burdened => cfg.burdened_move_cost,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Encumbrance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Encumbrance::Unburdened => "Unburdened",
+            Encumbrance::Burdened => "Burdened",
+            Encumbrance::Overloaded => "Overloaded",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How much someone as strong as `stats` can carry before they're burdened.
+pub fn capacity(stats: &components::CombatStats, cfg: &config::Encumbrance) -> i32 {
+    cfg.base_capacity + stats.power * cfg.per_power
+}
+
+/// The most that can be carried at all; pickups that would go past it are
+/// refused.
+pub fn overload_limit(capacity: i32, cfg: &config::Encumbrance) -> i32 {
+    capacity * cfg.overloaded_percent / 100
+}
+
+/// What an entity is carrying and how well they're coping with it.
+#[derive(Clone, Copy, Debug)]
+pub struct Load {
+    pub carried: i32,
+    pub capacity: i32,
+    pub encumbrance: Encumbrance,
+}
+
+/// The total weight of everything an entity has in their pack or is wearing.
+/// The pack can be read from either a read or a write storage.
+pub fn carried<D>(
+    entity: Entity,
+    backpack: &Storage<components::InBackpack, D>,
+    equipped: &ReadStorage<components::Equipped>,
+    weights: &ReadStorage<components::Weight>,
+) -> i32
+where
+    D: Deref<Target = MaskedStorage<components::InBackpack>>,
+{
+    let packed: i32 = (backpack, weights)
+        .join()
+        .filter(|(pack, _)| pack.owner == entity)
+        .map(|(_, weight)| weight.weight)
+        .sum();
+    let worn: i32 = (equipped, weights)
+        .join()
+        .filter(|(worn, _)| worn.owner == entity)
+        .map(|(_, weight)| weight.weight)
+        .sum();
+    packed + worn
+}
+
+/// Works out how weighed down an entity is by what they carry.
+pub fn load(ecs: &World, entity: Entity) -> Option<Load> {
+    let cfg = ecs.fetch::<config::AppConfig>();
+    let combat_stats = ecs.read_storage::<components::CombatStats>();
+    let backpack = ecs.read_storage::<components::InBackpack>();
+    let equipped = ecs.read_storage::<components::Equipped>();
+    let weights = ecs.read_storage::<components::Weight>();
+
+    let stats = combat_stats.get(entity)?;
+    let carried = carried(entity, &backpack, &equipped, &weights);
+    let capacity = capacity(stats, &cfg.encumbrance);
+    Some(Load {
+        carried,
+        capacity,
+        encumbrance: Encumbrance::of(carried, capacity, &cfg.encumbrance),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg() -> config::Encumbrance {
+        config::Encumbrance {
+            base_capacity: 30,
+            per_power: 5,
+            overloaded_percent: 150,
+            burdened_move_cost: 50,
+        }
+    }
+
+    #[test]
+    fn carrying_up_to_capacity_is_unburdened() {
+        assert_eq!(Encumbrance::of(0, 40, &cfg()), Encumbrance::Unburdened);
+        assert_eq!(Encumbrance::of(40, 40, &cfg()), Encumbrance::Unburdened);
+    }
+
+    #[test]
+    fn carrying_past_capacity_is_burdened_up_to_the_overload_limit() {
+        assert_eq!(overload_limit(40, &cfg()), 60);
+        assert_eq!(Encumbrance::of(41, 40, &cfg()), Encumbrance::Burdened);
+        assert_eq!(Encumbrance::of(60, 40, &cfg()), Encumbrance::Burdened);
+        assert_eq!(Encumbrance::of(61, 40, &cfg()), Encumbrance::Overloaded);
+    }
+
+    #[test]
+    fn only_the_burdened_pay_extra_to_move() {
+        let cfg = cfg();
+        assert_eq!(Encumbrance::Unburdened.move_penalty(&cfg), 0);
+        assert_eq!(Encumbrance::Burdened.move_penalty(&cfg), 50);
+        assert_eq!(Encumbrance::Overloaded.move_penalty(&cfg), 0);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::map::{Map, RevealedMap, TileType};
+use rltk::{DijkstraMap, Point};
+
+/// Revealed floor tiles next to something the player hasn't seen yet. The
+/// map's outer edge is always wall, so it never counts as unexplored.
+fn frontier(game_map: &Map) -> Vec<usize> {
+    let mut tiles: Vec<usize> = Vec::new();
+    for y in 1..game_map.height - 1 {
+        for x in 1..game_map.width - 1 {
+            let idx = game_map.xy_idx(x, y);
+            if !game_map.revealed_tiles[idx] || game_map.tiles[idx] == TileType::Wall {
+                continue;
+            }
+            let mut unexplored = false;
+            for (dx, dy) in &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ] {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < 1 || ny < 1 || nx > game_map.width - 2 || ny > game_map.height - 2 {
+                    continue;
+                }
+                if !game_map.revealed_tiles[game_map.xy_idx(nx, ny)] {
+                    unexplored = true;
+                    break;
+                }
+            }
+            if unexplored {
+                tiles.push(idx);
+            }
+        }
+    }
+    tiles
+}
+
+/// The next step towards the nearest unexplored part of the map, as a
+/// (dx, dy) delta. Nothing is returned once everything reachable has been
+/// seen.
+pub fn next_step(game_map: &Map, from: Point) -> Option<(i32, i32)> {
+    let here = game_map.xy_idx(from.x, from.y);
+    let mut starts = frontier(game_map);
+    starts.retain(|idx| *idx != here);
+    if starts.is_empty() {
+        return None;
+    }
+    let known = RevealedMap { map: game_map };
+    let dijkstra = DijkstraMap::new(
+        game_map.width as usize,
+        game_map.height as usize,
+        &starts,
+        &known,
+        (game_map.width + game_map.height) as f32 * 2.0,
+    );
+    // Whatever is left is out of reach
+    if dijkstra.map[here] == std::f32::MAX {
+        return None;
+    }
+    let next = DijkstraMap::find_lowest_exit(&dijkstra, here, &known)?;
+    if dijkstra.map[next] >= dijkstra.map[here] {
+        return None;
+    }
+    let x = next as i32 % game_map.width;
+    let y = next as i32 / game_map.width;
+    Some((x - from.x, y - from.y))
+}
@@ -0,0 +1,109 @@
+use crate::components;
+use crate::config;
+use crate::game;
+use crate::game::log::Category;
+use crate::player::character;
+use specs::prelude::*;
+
+/// F1 to F9.
+pub const SLOTS: usize = 9;
+
+impl components::Hotbar {
+    /// The slots a new character starts with, from the item keys in the
+    /// config.
+    pub fn from_config(cfg: &config::AppConfig) -> Self {
+        let mut slots: Vec<Option<String>> = cfg
+            .hotbar
+            .iter()
+            .take(SLOTS)
+            .map(|key| match cfg.items.get(key) {
+                None => Some(key.clone()),
+                Some(item) => Some(item.name.clone()),
+            })
+            .collect();
+        slots.resize(SLOTS, None);
+        components::Hotbar { slots }
+    }
+}
+
+/// An item type bound to a hotbar slot, and how many of it the player has.
+pub struct Slot {
+    pub name: String,
+    pub items: Vec<Entity>,
+}
+
+/// Every slot, each with the matching items from the player's pack, or
+/// nothing if it's empty.
+pub fn slots(ecs: &World) -> Vec<Option<Slot>> {
+    let player_entity = *ecs.fetch::<Entity>();
+    let names = ecs.read_storage::<components::Name>();
+    let backpack = ecs.read_storage::<components::InBackpack>();
+    let entities = ecs.entities();
+    let hotbar = match ecs.read_storage::<components::Hotbar>().get(player_entity) {
+        Some(hotbar) => hotbar.clone(),
+        None => components::Hotbar::from_config(&ecs.fetch::<config::AppConfig>()),
+    };
+
+    hotbar
+        .slots
+        .into_iter()
+        .map(|name| {
+            let name = name?;
+            let items = (&entities, &backpack, &names)
+                .join()
+                .filter(|(_, pack, n)| pack.owner == player_entity && n.name == name)
+                .map(|(entity, _, _)| entity)
+                .collect();
+            Some(Slot { name, items })
+        })
+        .collect()
+}
+
+/// Puts the kind of item `item` is into a slot, taking it out of any other
+/// slot it was in.
+pub fn assign(ecs: &World, slot: usize, item: Entity) {
+    let player_entity = *ecs.fetch::<Entity>();
+    let name = match ecs.read_storage::<components::Name>().get(item) {
+        None => return,
+        Some(name) => name.name.clone(),
+    };
+    let mut hotbars = ecs.write_storage::<components::Hotbar>();
+    if hotbars.get(player_entity).is_none() {
+        hotbars
+            .insert(
+                player_entity,
+                components::Hotbar::from_config(&ecs.fetch::<config::AppConfig>()),
+            )
+            .expect("Unable to insert hotbar");
+    }
+    if let Some(hotbar) = hotbars.get_mut(player_entity) {
+        for other in hotbar.slots.iter_mut() {
+            if other.as_ref() == Some(&name) {
+                *other = None;
+            }
+        }
+        hotbar.slots[slot] = Some(name.clone());
+    }
+    let mut gamelog = ecs.fetch_mut::<game::log::GameLog>();
+    gamelog.add(
+        Category::Item,
+        format!("The {} is now on F{}.", name, slot + 1),
+    );
+}
+
+/// Uses one of whatever's in a slot, just as picking it from the inventory
+/// would; ranged items go straight to choosing a target.
+pub fn activate(ecs: &World, slot: usize) -> game::state::RunState {
+    let item = match slots(ecs).into_iter().nth(slot).flatten() {
+        None => return game::state::RunState::AwaitingInput,
+        Some(slot) => match slot.items.first() {
+            Some(item) => *item,
+            None => {
+                let mut gamelog = ecs.fetch_mut::<game::log::GameLog>();
+                gamelog.add(Category::Item, format!("You have no {} left.", slot.name));
+                return game::state::RunState::AwaitingInput;
+            }
+        },
+    };
+    character::use_item(ecs, item)
+}
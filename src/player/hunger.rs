@@ -0,0 +1,139 @@
+use crate::combat::damage::DamageType;
+use crate::components;
+use crate::config;
+use crate::game;
+use crate::game::log::Category;
+use crate::game::turns;
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+use specs::{self, Join, System};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub enum HungerState {
+    WellFed,
+    Normal,
+    Hungry,
+    Weak,
+    Starving,
+}
+
+impl HungerState {
+    /// The state that follows once this one runs out.
+    pub fn next(self) -> HungerState {
+        match self {
+            HungerState::WellFed => HungerState::Normal,
+            HungerState::Normal => HungerState::Hungry,
+            HungerState::Hungry => HungerState::Weak,
+            HungerState::Weak | HungerState::Starving => HungerState::Starving,
+        }
+    }
+
+    /// How many turns are spent in the state. Starving lasts until
+    /// something is eaten.
+    pub fn duration(self, cfg: &config::Hunger) -> i32 {
+        match self {
+            HungerState::WellFed => cfg.well_fed,
+            HungerState::Normal => cfg.normal,
+            HungerState::Hungry => cfg.hungry,
+            HungerState::Weak => cfg.weak,
+            HungerState::Starving => 0,
+        }
+    }
+
+    /// Whether someone this hungry is too weak to heal naturally.
+    pub fn stops_healing(self) -> bool {
+        self == HungerState::Weak || self == HungerState::Starving
+    }
+
+    /// How much is taken off the power of someone this hungry.
+    pub fn penalty(self, cfg: &config::Hunger) -> i32 {
+        match self {
+            HungerState::Weak => cfg.weak_penalty,
+            HungerState::Starving => cfg.starving_penalty,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for HungerState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            HungerState::WellFed => "Well Fed",
+            HungerState::Normal => "Normal",
+            HungerState::Hungry => "Hungry",
+            HungerState::Weak => "Weak",
+            HungerState::Starving => "Starving",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl components::Hunger {
+    pub fn well_fed(cfg: &config::Hunger) -> Self {
+        components::Hunger {
+            state: HungerState::WellFed,
+            clock: HungerState::WellFed.duration(cfg),
+        }
+    }
+
+    /// Eating resets the clock, however hungry one was.
+    pub fn eat(&mut self, nutrition: i32) {
+        self.state = HungerState::WellFed;
+        self.clock = nutrition;
+    }
+}
+
+/// Counts hunger down once per turn of the clock, moving on to the next state
+/// when the current one runs out. Starvation costs HP every turn.
+pub struct HungerSystem {}
+
+impl<'a> System<'a> for HungerSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, config::AppConfig>,
+        ReadExpect<'a, turns::Clock>,
+        WriteExpect<'a, game::log::GameLog>,
+        WriteStorage<'a, components::Hunger>,
+        WriteStorage<'a, components::SufferDamage>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, player_entity, cfg, clock, mut log, mut hunger, mut suffer_damage) = data;
+
+        if !clock.advanced {
+            return;
+        }
+
+        for (entity, hunger) in (&entities, &mut hunger).join() {
+            if hunger.state == HungerState::Starving {
+                components::SufferDamage::new_damage(
+                    &mut suffer_damage,
+                    entity,
+                    cfg.hunger.starving_damage,
+                    DamageType::Starvation,
+                );
+                continue;
+            }
+
+            hunger.clock -= 1;
+            if hunger.clock > 0 {
+                continue;
+            }
+            hunger.state = hunger.state.next();
+            hunger.clock = hunger.state.duration(&cfg.hunger);
+            if entity == *player_entity {
+                let message = match hunger.state {
+                    HungerState::WellFed => continue,
+                    HungerState::Normal => "You are no longer well fed.",
+                    HungerState::Hungry => "You are hungry.",
+                    HungerState::Weak => "You are weak with hunger.",
+                    HungerState::Starving => "You are starving!",
+                };
+                log.add(Category::System, message);
+            }
+        }
+    }
+}
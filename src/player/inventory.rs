@@ -1,27 +1,90 @@
+use crate::combat::damage::DamageType;
+use crate::combat::projectile;
 use crate::components;
+use crate::config;
 use crate::game;
+use crate::game::log::Category;
 use crate::map;
+use crate::player::encumbrance;
+use rltk::Point;
 use specs;
 use specs::prelude::*;
 
+/// Gives an item that's just gone into its owner's pack the letter its kind
+/// is listed under, if the owner keeps letters and it hasn't got one yet.
+pub fn give_letter(
+    letters: &mut WriteStorage<components::InventoryLetters>,
+    backpack: &WriteStorage<components::InBackpack>,
+    names: &ReadStorage<components::Name>,
+    owner: Entity,
+    item: Entity,
+) {
+    let (letters, name) = match (letters.get_mut(owner), names.get(item)) {
+        (Some(letters), Some(name)) => (letters, name),
+        _ => return,
+    };
+    let carried: Vec<String> = (backpack, names)
+        .join()
+        .filter(|(pack, _)| pack.owner == owner)
+        .map(|(_, name)| name.name.clone())
+        .collect();
+    letters.letter_for(&name.name, &carried);
+}
+
 pub struct ItemCollectionSystem {}
 
 impl<'a> System<'a> for ItemCollectionSystem {
     #[allow(clippy::type_complexity)]
     type SystemData = (
         ReadExpect<'a, Entity>,
+        ReadExpect<'a, config::AppConfig>,
         WriteExpect<'a, game::log::GameLog>,
         WriteStorage<'a, components::WantsToPickupItem>,
         WriteStorage<'a, components::Position>,
         ReadStorage<'a, components::Name>,
         WriteStorage<'a, components::InBackpack>,
+        ReadStorage<'a, components::Equipped>,
+        ReadStorage<'a, components::CombatStats>,
+        ReadStorage<'a, components::Weight>,
+        WriteStorage<'a, components::InventoryLetters>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (player_entity, mut gamelog, mut wants_pickup, mut positions, names, mut backpack) =
-            data;
+        let (
+            player_entity,
+            cfg,
+            mut gamelog,
+            mut wants_pickup,
+            mut positions,
+            names,
+            mut backpack,
+            equipped,
+            combat_stats,
+            weights,
+            mut letters,
+        ) = data;
 
         for pickup in wants_pickup.join() {
+            // Nothing goes in a pack that would leave its owner unable to move
+            if let Some(stats) = combat_stats.get(pickup.collected_by) {
+                let owner = pickup.collected_by;
+                let carried = encumbrance::carried(owner, &backpack, &equipped, &weights);
+                let weight = weights.get(pickup.item).map_or(0, |w| w.weight);
+                let capacity = encumbrance::capacity(stats, &cfg.encumbrance);
+                if carried + weight > encumbrance::overload_limit(capacity, &cfg.encumbrance) {
+                    if owner == *player_entity {
+                        gamelog.add(
+                            Category::Item,
+                            format!(
+                                "You can't carry the {} as well; your pack is full.",
+                                names.get(pickup.item).unwrap().name
+                            ),
+                        );
+                    }
+                    continue;
+                }
+            }
+
             positions.remove(pickup.item);
             backpack
                 .insert(
@@ -31,12 +94,19 @@ impl<'a> System<'a> for ItemCollectionSystem {
                     },
                 )
                 .expect("Unable to insert backpack entry");
+            give_letter(
+                &mut letters,
+                &backpack,
+                &names,
+                pickup.collected_by,
+                pickup.item,
+            );
 
             if pickup.collected_by == *player_entity {
-                gamelog.entries.push(format!(
-                    "You pick up the {}.",
-                    names.get(pickup.item).unwrap().name
-                ));
+                gamelog.add(
+                    Category::Item,
+                    format!("You pick up the {}.", names.get(pickup.item).unwrap().name),
+                );
             }
         }
 
@@ -61,7 +131,13 @@ impl<'a> System<'a> for ItemUseSystem {
         WriteStorage<'a, components::CombatStats>,
         WriteStorage<'a, components::SufferDamage>,
         ReadStorage<'a, components::AreaOfEffect>,
-        WriteStorage<'a, components::Confusion>,
+        ReadStorage<'a, components::InflictsStatus>,
+        WriteStorage<'a, components::StatusEffects>,
+        WriteStorage<'a, components::LastHitBy>,
+        WriteStorage<'a, components::AfflictedBy>,
+        ReadStorage<'a, components::ProvidesFood>,
+        WriteStorage<'a, components::Hunger>,
+        ReadStorage<'a, components::Position>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -78,7 +154,13 @@ impl<'a> System<'a> for ItemUseSystem {
             mut combat_stats,
             mut suffer_damage,
             aoe,
-            mut confused,
+            inflict_status,
+            mut statuses,
+            mut last_hit,
+            mut afflicted_by,
+            food,
+            mut hunger,
+            positions,
         ) = data;
 
         for (entity, useitem) in (&entities, &wants_use).join() {
@@ -94,7 +176,14 @@ impl<'a> System<'a> for ItemUseSystem {
                     let area_effect = aoe.get(useitem.item);
                     match area_effect {
                         None => {
-                            // Single target in tile
+                            // Single target: the bolt stops at the first
+                            // thing in its way
+                            let target = match positions.get(entity) {
+                                None => target,
+                                Some(pos) => {
+                                    projectile::impact(&game_map, Point::new(pos.x, pos.y), target)
+                                }
+                            };
                             let idx = game_map.xy_idx(target.x, target.y);
                             for mob in game_map.tile_content[idx].iter() {
                                 targets.push(*mob);
@@ -121,6 +210,23 @@ impl<'a> System<'a> for ItemUseSystem {
                 }
             }
 
+            // If it's edible, eat it
+            if let Some(food) = food.get(useitem.item) {
+                used_item = false;
+                for target in targets.iter() {
+                    if let Some(hunger) = hunger.get_mut(*target) {
+                        hunger.eat(food.nutrition);
+                        if entity == *player_entity {
+                            gamelog.add(
+                                Category::Item,
+                                format!("You eat the {}.", names.get(useitem.item).unwrap().name),
+                            );
+                        }
+                        used_item = true;
+                    }
+                }
+            }
+
             // If it heals, apply the healing
             let item_heals = healing.get(useitem.item);
             match item_heals {
@@ -132,11 +238,14 @@ impl<'a> System<'a> for ItemUseSystem {
                         if let Some(stats) = stats {
                             stats.hp = i32::min(stats.max_hp, stats.hp + healer.heal_amount);
                             if entity == *player_entity {
-                                gamelog.entries.push(format!(
-                                    "You use the {}, healing {} hp.",
-                                    names.get(useitem.item).unwrap().name,
-                                    healer.heal_amount
-                                ));
+                                gamelog.add(
+                                    Category::Item,
+                                    format!(
+                                        "You use the {}, healing {} hp.",
+                                        names.get(useitem.item).unwrap().name,
+                                        healer.heal_amount
+                                    ),
+                                );
                             }
                             used_item = true;
                         }
@@ -151,18 +260,28 @@ impl<'a> System<'a> for ItemUseSystem {
                 Some(damage) => {
                     used_item = false;
                     for mob in targets.iter() {
+                        last_hit
+                            .insert(*mob, components::LastHitBy { attacker: entity })
+                            .expect("Unable to insert last hit");
                         components::SufferDamage::new_damage(
                             &mut suffer_damage,
                             *mob,
                             damage.damage,
+                            damage.damage_type,
                         );
                         if entity == *player_entity {
                             let mob_name = names.get(*mob).unwrap();
                             let item_name = names.get(useitem.item).unwrap();
-                            gamelog.entries.push(format!(
-                                "You use {} on {}, inflicting {} hp.",
-                                item_name.name, mob_name.name, damage.damage
-                            ));
+                            gamelog.add(
+                                Category::Item,
+                                format!(
+                                    "You use {} on {}, inflicting {} hp of {} damage.",
+                                    item_name.name,
+                                    mob_name.name,
+                                    damage.damage,
+                                    damage.damage_type
+                                ),
+                            );
                         }
 
                         used_item = true;
@@ -170,33 +289,47 @@ impl<'a> System<'a> for ItemUseSystem {
                 }
             }
 
-            // Can it pass along confusion? Note the use of scopes to escape from the borrow checker!
-            let mut add_confusion = Vec::new();
-            {
-                let causes_confusion = confused.get(useitem.item);
-                match causes_confusion {
-                    None => {}
-                    Some(confusion) => {
-                        used_item = false;
-                        for mob in targets.iter() {
-                            add_confusion.push((*mob, confusion.turns));
-                            if entity == *player_entity {
+            // Does it pass along a status effect?
+            let item_afflicts = inflict_status.get(useitem.item);
+            match item_afflicts {
+                None => {}
+                Some(afflicts) => {
+                    used_item = false;
+                    for mob in targets.iter() {
+                        components::StatusEffects::apply(&mut statuses, *mob, afflicts.effect);
+                        if *mob != entity {
+                            afflicted_by
+                                .insert(*mob, components::AfflictedBy { attacker: entity })
+                                .expect("Unable to insert affliction source");
+                        }
+                        if entity == *player_entity {
+                            let item_name = names.get(useitem.item).unwrap();
+                            if *mob == *player_entity {
+                                gamelog.add(
+                                    Category::Item,
+                                    format!(
+                                        "You use the {}, and are now {}.",
+                                        item_name.name,
+                                        afflicts.effect.kind.adjective()
+                                    ),
+                                );
+                            } else {
                                 let mob_name = names.get(*mob).unwrap();
-                                let item_name = names.get(useitem.item).unwrap();
-                                gamelog.entries.push(format!(
-                                    "You use {} on {}, confusing them.",
-                                    item_name.name, mob_name.name
-                                ));
+                                gamelog.add(
+                                    Category::Item,
+                                    format!(
+                                        "You use {} on {}, who is now {}.",
+                                        item_name.name,
+                                        mob_name.name,
+                                        afflicts.effect.kind.adjective()
+                                    ),
+                                );
                             }
                         }
+                        used_item = true;
                     }
                 }
             }
-            for mob in add_confusion.iter() {
-                confused
-                    .insert(mob.0, components::Confusion { turns: mob.1 })
-                    .expect("Unable to insert status");
-            }
 
             // If its a consumable, we delete it on use
             if used_item {
@@ -258,13 +391,313 @@ impl<'a> System<'a> for ItemDropSystem {
             backpack.remove(to_drop.item);
 
             if entity == *player_entity {
-                gamelog.entries.push(format!(
-                    "You drop the {}.",
-                    names.get(to_drop.item).unwrap().name
-                ));
+                gamelog.add(
+                    Category::Item,
+                    format!("You drop the {}.", names.get(to_drop.item).unwrap().name),
+                );
             }
         }
 
         wants_drop.clear();
     }
 }
+
+/// How far an entity can throw something; the strong throw further.
+pub fn throw_range(ecs: &World, entity: Entity) -> i32 {
+    let cfg = ecs.fetch::<config::AppConfig>();
+    let power = ecs
+        .read_storage::<components::CombatStats>()
+        .get(entity)
+        .map_or(0, |stats| stats.power);
+    cfg.throwing.base_range + power / i32::max(1, cfg.throwing.power_per_tile)
+}
+
+/// Sends thrown items flying. They stop at the first thing in their way,
+/// like bolts do. Potions shatter there and work on the creatures they
+/// splash; anything else lands on the spot, hurting what it hits if it's
+/// heavy enough.
+pub struct ThrowSystem {}
+
+impl<'a> System<'a> for ThrowSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, config::AppConfig>,
+        WriteExpect<'a, game::log::GameLog>,
+        ReadExpect<'a, map::Map>,
+        Entities<'a>,
+        WriteStorage<'a, components::WantsToThrowItem>,
+        ReadStorage<'a, components::Name>,
+        WriteStorage<'a, components::Position>,
+        WriteStorage<'a, components::InBackpack>,
+        ReadStorage<'a, components::Weight>,
+        ReadStorage<'a, components::Consumable>,
+        ReadStorage<'a, components::Ranged>,
+        ReadStorage<'a, components::ProvidesHealing>,
+        ReadStorage<'a, components::InflictsStatus>,
+        WriteStorage<'a, components::CombatStats>,
+        WriteStorage<'a, components::StatusEffects>,
+        WriteStorage<'a, components::AfflictedBy>,
+        WriteStorage<'a, components::SufferDamage>,
+        WriteStorage<'a, components::LastHitBy>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            player_entity,
+            cfg,
+            mut gamelog,
+            game_map,
+            entities,
+            mut wants_throw,
+            names,
+            mut positions,
+            mut backpack,
+            weights,
+            consumables,
+            ranged,
+            healing,
+            inflict_status,
+            mut combat_stats,
+            mut statuses,
+            mut afflicted_by,
+            mut suffer_damage,
+            mut last_hit,
+        ) = data;
+
+        for (entity, throw) in (&entities, &wants_throw).join() {
+            let from = match positions.get(entity) {
+                None => continue,
+                Some(pos) => Point::new(pos.x, pos.y),
+            };
+            let landing = projectile::landing(&game_map, from, throw.target);
+            let idx = game_map.xy_idx(landing.x, landing.y);
+            let hit: Vec<Entity> = game_map.tile_content[idx]
+                .iter()
+                .filter(|mob| combat_stats.get(**mob).is_some())
+                .copied()
+                .collect();
+            let item_name = names.get(throw.item).unwrap().name.clone();
+            backpack.remove(throw.item);
+
+            // Potions break, and whoever they land on gets the dose
+            let fragile = consumables.get(throw.item).is_some()
+                && ranged.get(throw.item).is_none()
+                && (healing.get(throw.item).is_some() || inflict_status.get(throw.item).is_some());
+            if fragile {
+                if entity == *player_entity {
+                    gamelog.add(Category::Item, format!("The {} shatters!", item_name));
+                }
+                for mob in hit.iter() {
+                    let mob_name = &names.get(*mob).unwrap().name;
+                    if let Some(healer) = healing.get(throw.item) {
+                        if let Some(stats) = combat_stats.get_mut(*mob) {
+                            stats.hp = i32::min(stats.max_hp, stats.hp + healer.heal_amount);
+                        }
+                        if entity == *player_entity {
+                            gamelog.add(
+                                Category::Item,
+                                format!(
+                                    "The {} splashes over {}, healing {} hp.",
+                                    item_name, mob_name, healer.heal_amount
+                                ),
+                            );
+                        }
+                    }
+                    if let Some(afflicts) = inflict_status.get(throw.item) {
+                        components::StatusEffects::apply(&mut statuses, *mob, afflicts.effect);
+                        if *mob != entity {
+                            afflicted_by
+                                .insert(*mob, components::AfflictedBy { attacker: entity })
+                                .expect("Unable to insert affliction source");
+                        }
+                        if entity == *player_entity {
+                            gamelog.add(
+                                Category::Item,
+                                format!(
+                                    "The {} splashes over {}, who is now {}.",
+                                    item_name,
+                                    mob_name,
+                                    afflicts.effect.kind.adjective()
+                                ),
+                            );
+                        }
+                    }
+                }
+                entities.delete(throw.item).expect("Delete failed");
+                continue;
+            }
+
+            positions
+                .insert(
+                    throw.item,
+                    components::Position {
+                        x: landing.x,
+                        y: landing.y,
+                    },
+                )
+                .expect("Unable to insert position");
+
+            let weight = weights.get(throw.item).map_or(0, |w| w.weight);
+            let damage = weight / i32::max(1, cfg.throwing.weight_per_damage);
+            let victims: Vec<Entity> = hit.into_iter().filter(|mob| *mob != entity).collect();
+            if damage > 0 && !victims.is_empty() {
+                for mob in victims.iter() {
+                    last_hit
+                        .insert(*mob, components::LastHitBy { attacker: entity })
+                        .expect("Unable to insert last hit");
+                    components::SufferDamage::new_damage(
+                        &mut suffer_damage,
+                        *mob,
+                        damage,
+                        DamageType::Physical,
+                    );
+                    if entity == *player_entity {
+                        gamelog.add(
+                            Category::Combat,
+                            format!(
+                                "The {} hits {}, for {} hp.",
+                                item_name,
+                                names.get(*mob).unwrap().name,
+                                damage
+                            ),
+                        );
+                    }
+                }
+            } else if entity == *player_entity {
+                gamelog.add(Category::Item, format!("You throw the {}.", item_name));
+            }
+        }
+
+        wants_throw.clear();
+    }
+}
+
+pub struct ItemEquipSystem {}
+
+impl<'a> System<'a> for ItemEquipSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, game::log::GameLog>,
+        Entities<'a>,
+        WriteStorage<'a, components::WantsToEquipItem>,
+        ReadStorage<'a, components::Name>,
+        ReadStorage<'a, components::Equippable>,
+        WriteStorage<'a, components::Equipped>,
+        WriteStorage<'a, components::InBackpack>,
+        WriteStorage<'a, components::InventoryLetters>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            player_entity,
+            mut gamelog,
+            entities,
+            mut wants_equip,
+            names,
+            equippable,
+            mut equipped,
+            mut backpack,
+            mut letters,
+        ) = data;
+
+        for (entity, to_equip) in (&entities, &wants_equip).join() {
+            let slot = match equippable.get(to_equip.item) {
+                None => continue,
+                Some(equippable) => equippable.slot,
+            };
+
+            // Make room by taking off one of the items already worn there;
+            // which one is left to storage order
+            let worn: Vec<Entity> = (&entities, &equipped)
+                .join()
+                .filter(|(_, e)| e.owner == entity && e.slot == slot)
+                .map(|(item, _)| item)
+                .collect();
+            if worn.len() >= slot.capacity() {
+                let item = worn[0];
+                equipped.remove(item);
+                backpack
+                    .insert(item, components::InBackpack { owner: entity })
+                    .expect("Unable to insert backpack entry");
+                give_letter(&mut letters, &backpack, &names, entity, item);
+                if entity == *player_entity {
+                    gamelog.add(
+                        Category::Item,
+                        format!("You take off the {}.", names.get(item).unwrap().name),
+                    );
+                }
+            }
+
+            equipped
+                .insert(
+                    to_equip.item,
+                    components::Equipped {
+                        owner: entity,
+                        slot,
+                    },
+                )
+                .expect("Unable to insert equipped component");
+            backpack.remove(to_equip.item);
+
+            if entity == *player_entity {
+                gamelog.add(
+                    Category::Item,
+                    format!("You equip the {}.", names.get(to_equip.item).unwrap().name),
+                );
+            }
+        }
+
+        wants_equip.clear();
+    }
+}
+
+pub struct ItemRemoveSystem {}
+
+impl<'a> System<'a> for ItemRemoveSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, game::log::GameLog>,
+        Entities<'a>,
+        WriteStorage<'a, components::WantsToRemoveItem>,
+        ReadStorage<'a, components::Name>,
+        WriteStorage<'a, components::Equipped>,
+        WriteStorage<'a, components::InBackpack>,
+        WriteStorage<'a, components::InventoryLetters>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            player_entity,
+            mut gamelog,
+            entities,
+            mut wants_remove,
+            names,
+            mut equipped,
+            mut backpack,
+            mut letters,
+        ) = data;
+
+        for (entity, to_remove) in (&entities, &wants_remove).join() {
+            equipped.remove(to_remove.item);
+            backpack
+                .insert(to_remove.item, components::InBackpack { owner: entity })
+                .expect("Unable to insert backpack entry");
+            give_letter(&mut letters, &backpack, &names, entity, to_remove.item);
+
+            if entity == *player_entity {
+                gamelog.add(
+                    Category::Item,
+                    format!(
+                        "You take off the {}.",
+                        names.get(to_remove.item).unwrap().name
+                    ),
+                );
+            }
+        }
+
+        wants_remove.clear();
+    }
+}
@@ -1,3 +1,11 @@
+pub mod activity;
+pub mod bindings;
 pub mod character;
+pub mod creation;
+pub mod encumbrance;
+pub mod explore;
+pub mod hotbar;
+pub mod hunger;
 pub mod inventory;
+pub mod progression;
 pub mod user;
@@ -0,0 +1,136 @@
+use crate::components;
+use crate::config;
+use crate::game;
+use crate::game::log::{Category, Highlight, LogEntry};
+use specs::prelude::*;
+use std::fmt;
+
+/// The stats a player can raise when they level up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stat {
+    Power,
+    Defense,
+}
+
+impl fmt::Display for Stat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Stat::Power => "Power",
+            Stat::Defense => "Defense",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The total experience needed to reach a level. Past the end of the
+/// configured thresholds every level costs as much as the last one did.
+pub fn xp_for_level(level: i32, cfg: &config::Progression) -> i32 {
+    if level <= 1 {
+        return 0;
+    }
+    let idx = (level - 2) as usize;
+    if let Some(xp) = cfg.thresholds.get(idx) {
+        return *xp;
+    }
+    let count = cfg.thresholds.len();
+    let last = cfg.thresholds.last().copied().unwrap_or(0);
+    let previous = if count > 1 {
+        cfg.thresholds[count - 2]
+    } else {
+        0
+    };
+    let gap = i32::max(1, last - previous);
+    last + gap * (idx + 1 - count) as i32
+}
+
+impl components::Experience {
+    pub fn new() -> Self {
+        components::Experience {
+            level: 1,
+            xp: 0,
+            unspent: 0,
+        }
+    }
+
+    /// Adds experience and returns the number of levels gained. Every level
+    /// gained also earns a stat point to spend.
+    pub fn gain(&mut self, xp: i32, cfg: &config::Progression) -> i32 {
+        self.xp += xp;
+        let mut gained = 0;
+        while self.xp >= xp_for_level(self.level + 1, cfg) {
+            self.level += 1;
+            gained += 1;
+        }
+        self.unspent += gained;
+        gained
+    }
+
+    pub fn next_level_xp(&self, cfg: &config::Progression) -> i32 {
+        xp_for_level(self.level + 1, cfg)
+    }
+}
+
+impl Default for components::Experience {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gives experience to whoever landed the killing blow. Levelling up raises
+/// max HP straight away; the stat increase is left for the player to choose.
+pub fn award(ecs: &World, entity: Entity, xp: i32) {
+    let cfg = ecs.fetch::<config::AppConfig>();
+    let player_entity = ecs.fetch::<Entity>();
+    let mut experience = ecs.write_storage::<components::Experience>();
+    let mut combat_stats = ecs.write_storage::<components::CombatStats>();
+    let mut log = ecs.write_resource::<game::log::GameLog>();
+
+    if let Some(exp) = experience.get_mut(entity) {
+        let levels = exp.gain(xp, &cfg.progression);
+        if entity == *player_entity {
+            log.add(Category::System, format!("You gain {} experience.", xp));
+        }
+        if levels > 0 {
+            if let Some(stats) = combat_stats.get_mut(entity) {
+                let hp = levels * cfg.progression.hp_per_level;
+                stats.max_hp += hp;
+                stats.hp += hp;
+            }
+            if entity == *player_entity {
+                log.push(
+                    LogEntry::new(Category::System)
+                        .highlighted(format!("Welcome to level {}!", exp.level), Highlight::Good),
+                );
+            }
+        }
+    }
+}
+
+pub fn has_unspent_levels(ecs: &World, entity: Entity) -> bool {
+    let experience = ecs.read_storage::<components::Experience>();
+    experience.get(entity).map_or(false, |exp| exp.unspent > 0)
+}
+
+pub fn raise(ecs: &World, entity: Entity, stat: Stat) {
+    let cfg = ecs.fetch::<config::AppConfig>();
+    let mut experience = ecs.write_storage::<components::Experience>();
+    let mut combat_stats = ecs.write_storage::<components::CombatStats>();
+    let mut log = ecs.write_resource::<game::log::GameLog>();
+
+    if let (Some(exp), Some(stats)) = (experience.get_mut(entity), combat_stats.get_mut(entity)) {
+        if exp.unspent < 1 {
+            return;
+        }
+        exp.unspent -= 1;
+        match stat {
+            Stat::Power => {
+                stats.power += cfg.progression.stat_per_level;
+                log.add(Category::System, "You feel stronger.");
+            }
+            Stat::Defense => {
+                stats.defense += cfg.progression.stat_per_level;
+                log.add(Category::System, "You feel tougher.");
+            }
+        }
+    }
+}
@@ -1,19 +1,54 @@
 use crate::components;
+use crate::config;
+use crate::effects::status::{self, StatusKind};
+use crate::faction;
 use crate::game;
+use crate::game::log::Category;
+use crate::game::turns;
+use crate::gui::camera;
+use crate::gui::cursor;
 use crate::map;
+use crate::player::activity;
+use crate::player::bindings::{self, Command};
 use crate::player::character;
+use crate::player::encumbrance::{self, Encumbrance};
+use crate::player::hotbar;
 use log;
-use rltk::{Point, Rltk, VirtualKeyCode};
+use rltk::{Point, RandomNumberGenerator, Rltk};
 use specs;
 use specs::prelude::*;
 use std::cmp::{max, min};
 
-pub fn try_move(delta_x: i32, delta_y: i32, ecs: &mut specs::World) {
+/// A confused player only goes where they meant to about half of the time.
+fn stumble(delta_x: i32, delta_y: i32, ecs: &specs::World) -> (i32, i32) {
+    let player_entity = *ecs.fetch::<Entity>();
+    if !status::has_effect(ecs, player_entity, StatusKind::Confusion) {
+        return (delta_x, delta_y);
+    }
+    let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+    if rng.roll_dice(1, 2) == 1 {
+        return (delta_x, delta_y);
+    }
+    let (mut x, mut y) = (0, 0);
+    while x == 0 && y == 0 {
+        x = rng.range(-1, 2);
+        y = rng.range(-1, 2);
+    }
+    (x, y)
+}
+
+/// Moves the player, or attacks whatever hostile is in the way. Returns the
+/// action taken, if any, so the caller knows what it cost.
+pub fn try_move(delta_x: i32, delta_y: i32, ecs: &mut specs::World) -> Option<turns::Action> {
+    let (delta_x, delta_y) = stumble(delta_x, delta_y, ecs);
     let mut positions = ecs.write_storage::<components::Position>();
     let players = ecs.read_storage::<components::Player>();
     let mut viewsheds = ecs.write_storage::<components::Viewshed>();
     let entities = ecs.entities();
     let combat_stats = ecs.read_storage::<components::CombatStats>();
+    let factions = ecs.read_storage::<components::Faction>();
+    let names = ecs.read_storage::<components::Name>();
+    let cfg = ecs.fetch::<config::AppConfig>();
     let game_map = ecs.fetch::<map::Map>();
     let mut wants_to_melee = ecs.write_storage::<components::WantsToMelee>();
 
@@ -25,26 +60,48 @@ pub fn try_move(delta_x: i32, delta_y: i32, ecs: &mut specs::World) {
             || pos.y + delta_y < 1
             || pos.y + delta_y > game_map.height - 1
         {
-            return;
+            return None;
         }
         let destination_idx = game_map.xy_idx(pos.x + delta_x, pos.y + delta_y);
 
         for potential_target in game_map.tile_content[destination_idx].iter() {
-            let target = combat_stats.get(*potential_target);
-            if let Some(_target) = target {
-                wants_to_melee
-                    .insert(
-                        entity,
-                        components::WantsToMelee {
-                            target: *potential_target,
-                        },
-                    )
-                    .expect("Add target failed");
-                return;
+            if combat_stats.get(*potential_target).is_none() {
+                continue;
+            }
+            let reaction = faction::between(
+                &cfg.factions,
+                factions.get(entity),
+                factions.get(*potential_target),
+            );
+            if reaction != faction::Reaction::Hostile {
+                // Only walk into hostiles with your weapon drawn
+                if let Some(name) = names.get(*potential_target) {
+                    let mut gamelog = ecs.fetch_mut::<game::log::GameLog>();
+                    gamelog.add(Category::Combat, format!("{} is in your way.", name.name));
+                }
+                return None;
             }
+            wants_to_melee
+                .insert(
+                    entity,
+                    components::WantsToMelee {
+                        target: *potential_target,
+                    },
+                )
+                .expect("Add target failed");
+            return Some(turns::Action::Attack);
         }
 
         if !game_map.blocked[destination_idx] {
+            let load = encumbrance::load(ecs, entity);
+            if load.map(|load| load.encumbrance) == Some(Encumbrance::Overloaded) {
+                let mut gamelog = ecs.fetch_mut::<game::log::GameLog>();
+                gamelog.add(
+                    Category::Item,
+                    "You are carrying too much to move. Drop something first.",
+                );
+                return None;
+            }
             pos.x = min(game_map.width - 1, max(0, pos.x + delta_x));
             pos.y = min(game_map.height - 1, max(0, pos.y + delta_y));
 
@@ -52,56 +109,102 @@ pub fn try_move(delta_x: i32, delta_y: i32, ecs: &mut specs::World) {
             let mut ppos = ecs.write_resource::<Point>();
             ppos.x = pos.x;
             ppos.y = pos.y;
+            return Some(turns::Action::Move);
         }
     }
+    None
 }
 
 pub fn input(gs: &mut game::state::State, ctx: &mut Rltk) -> game::state::RunState {
-    // Player movement
-    match ctx.key {
+    // The paralyzed don't get a say in the matter
+    let player_entity = *gs.ecs.fetch::<Entity>();
+    if status::has_effect(&gs.ecs, player_entity, StatusKind::Paralysis) {
+        let mut gamelog = gs.ecs.fetch_mut::<game::log::GameLog>();
+        gamelog.add(Category::Combat, "You are paralyzed!");
+        turns::spend(&gs.ecs, player_entity, turns::Action::Wait);
+        return game::state::RunState::PlayerTurn;
+    }
+
+    // Clicking on the map travels there
+    if ctx.left_click {
+        if let Some(target) = camera::Camera::new(&gs.ecs).mouse(ctx) {
+            activity::travel_to(&gs.ecs, target);
+        }
+        return game::state::RunState::AwaitingInput;
+    }
+
+    let key = match ctx.key {
         None => return game::state::RunState::AwaitingInput, // Nothing happened
-        Some(key) => match key {
-            // Movement
-            VirtualKeyCode::Left | VirtualKeyCode::A | VirtualKeyCode::Key4 => {
-                try_move(-1, 0, &mut gs.ecs)
-            }
-            VirtualKeyCode::Right | VirtualKeyCode::D | VirtualKeyCode::Key6 => {
-                try_move(1, 0, &mut gs.ecs)
-            }
-            VirtualKeyCode::Up | VirtualKeyCode::W | VirtualKeyCode::Key8 => {
-                try_move(0, -1, &mut gs.ecs)
-            }
-            VirtualKeyCode::Down | VirtualKeyCode::S | VirtualKeyCode::Key2 => {
-                try_move(0, 1, &mut gs.ecs)
-            }
-            VirtualKeyCode::Key7 | VirtualKeyCode::Q => try_move(-1, -1, &mut gs.ecs),
-            VirtualKeyCode::Key9 | VirtualKeyCode::E => try_move(1, -1, &mut gs.ecs),
-            VirtualKeyCode::Key3 | VirtualKeyCode::C => try_move(1, 1, &mut gs.ecs),
-            VirtualKeyCode::Key1 | VirtualKeyCode::Z => try_move(-1, 1, &mut gs.ecs),
-            // Items management
-            VirtualKeyCode::P => character::get_item(&mut gs.ecs), // pick-up
-            VirtualKeyCode::I => return game::state::RunState::ShowInventory,
-            VirtualKeyCode::L => return game::state::RunState::ShowDropItem, // let-go
+        Some(key) => key,
+    };
+    let command = match bindings::command(key, ctx.shift) {
+        None => {
+            log::debug!("Got user input: {:?}", key);
+            return game::state::RunState::AwaitingInput;
+        }
+        Some(command) => command,
+    };
+    let action = match command {
+        // Movement
+        Command::Move(delta_x, delta_y) => try_move(delta_x, delta_y, &mut gs.ecs),
 
-            // Entering/leaving map areas
-            VirtualKeyCode::Return => {
-                if character::try_next_level(&mut gs.ecs) {
-                    return game::state::RunState::NextLevel;
-                }
-            }
+        // Passing time
+        Command::Wait => Some(turns::Action::Wait),
+        Command::Rest => {
+            activity::start(&gs.ecs, activity::Activity::Resting);
+            None
+        }
+        Command::Explore => {
+            activity::start(&gs.ecs, activity::Activity::Exploring);
+            None
+        }
+        Command::TravelToExit => {
+            activity::travel_to_exit(&gs.ecs);
+            None
+        }
 
-            // Main menu
-            VirtualKeyCode::Escape => return game::state::RunState::ShowMainMenu,
+        // Items management
+        Command::PickUp => character::get_item(&mut gs.ecs),
+        Command::Inventory => return game::state::RunState::ShowInventory,
+        Command::Drop => return game::state::RunState::ShowDropItem,
+        Command::Throw => return game::state::RunState::ShowThrowItem,
+        Command::Equipment => return game::state::RunState::ShowEquipment,
 
-            VirtualKeyCode::Space => {
-                log::debug!("Pausing game ...");
-                return game::state::RunState::Paused;
+        // Looking around
+        Command::Look => {
+            let player_pos = *gs.ecs.fetch::<Point>();
+            cursor::place(&gs.ecs, player_pos);
+            return game::state::RunState::Looking;
+        }
+
+        // Entering/leaving map areas
+        Command::Descend => {
+            if character::try_next_level(&mut gs.ecs) {
+                return game::state::RunState::NextLevel;
             }
-            _ => {
-                log::debug!("Got user input: {:?}", key);
-                return game::state::RunState::AwaitingInput;
+            None
+        }
+
+        // Information screens
+        Command::Character => return game::state::RunState::ShowCharacter,
+        Command::Messages => {
+            return game::state::RunState::ShowLog {
+                scroll: 0,
+                filter: None,
             }
-        },
+        }
+        Command::Help => return game::state::RunState::ShowHelp,
+        Command::Hotbar(slot) => return hotbar::activate(&gs.ecs, slot),
+        Command::AssignHotbar => return game::state::RunState::ShowHotbarItem,
+        Command::MainMenu => return game::state::RunState::ShowMainMenu,
+    };
+
+    // Only hand the turn over if the player actually did something
+    match action {
+        None => game::state::RunState::AwaitingInput,
+        Some(action) => {
+            turns::spend(&gs.ecs, player_entity, action);
+            game::state::RunState::PlayerTurn
+        }
     }
-    game::state::RunState::PlayerTurn
 }
@@ -24,7 +24,7 @@ pub fn spawn(ecs: &mut World, room: &rect::Rect, cfg: &config::AppConfig) {
             while !added {
                 let x = (room.x1 + rng.roll_dice(1, i32::abs(room.x2 - room.x1))) as usize;
                 let y = (room.y1 + rng.roll_dice(1, i32::abs(room.y2 - room.y1))) as usize;
-                let idx = (y * cfg.gui.map_area.width as usize) + x;
+                let idx = (y * cfg.map.width as usize) + x;
                 if !monster_spawn_points.contains(&idx) {
                     monster_spawn_points.push(idx);
                     added = true;
@@ -38,7 +38,7 @@ pub fn spawn(ecs: &mut World, room: &rect::Rect, cfg: &config::AppConfig) {
             while !added {
                 let x = (room.x1 + rng.roll_dice(1, i32::abs(room.x2 - room.x1))) as usize;
                 let y = (room.y1 + rng.roll_dice(1, i32::abs(room.y2 - room.y1))) as usize;
-                let idx = (y * cfg.gui.map_area.width as usize) + x;
+                let idx = (y * cfg.map.width as usize) + x;
                 if !item_spawn_points.contains(&idx) {
                     item_spawn_points.push(idx);
                     added = true;
@@ -49,16 +49,16 @@ pub fn spawn(ecs: &mut World, room: &rect::Rect, cfg: &config::AppConfig) {
 
     log::trace!("Checking to see if new room gets monsters ...");
     for idx in monster_spawn_points.iter() {
-        let x = *idx as i32 % cfg.gui.map_area.width;
-        let y = *idx as i32 / cfg.gui.map_area.width;
+        let x = *idx as i32 % cfg.map.width;
+        let y = *idx as i32 / cfg.map.width;
         let pos = components::Position { x: x, y: y };
         monster::random(ecs, pos, &cfg.monsters);
     }
 
     log::trace!("Checking to see if new room gets items ...");
     for idx in item_spawn_points.iter() {
-        let x = *idx as i32 % cfg.gui.map_area.width;
-        let y = *idx as i32 / cfg.gui.map_area.width;
+        let x = *idx as i32 % cfg.map.width;
+        let y = *idx as i32 / cfg.map.width;
         let pos = components::Position { x: x, y: y };
         items::random(ecs, pos, &cfg.items);
     }